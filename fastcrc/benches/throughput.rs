@@ -3,7 +3,7 @@ use std::sync::OnceLock;
 
 use digest::Digest;
 use divan::{black_box, counter::BytesCount, Bencher};
-use fastcrc::{Crc32, Crc32c};
+use fastcrc::{Crc32, Crc32c, Crc64Xz};
 
 fn main() {
     divan::main();
@@ -72,6 +72,7 @@ macro_rules! register_digest_bench {
 
 register_digest_bench!(bench_crc32, Crc32, "crc32");
 register_digest_bench!(bench_crc32c, Crc32c, "crc32c");
+register_digest_bench!(bench_crc64_xz, Crc64Xz, "crc64-xz");
 
 fn bench_digest<D>(bencher: Bencher, dataset: DatasetSpec)
 where
@@ -0,0 +1,288 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Asynchronous counterparts of the [`CrcReader`](crate::CrcReader) and
+//! [`CrcWriter`](crate::CrcWriter) adapters.
+//!
+//! The adapters implement tokio's traits with the `tokio` feature and
+//! futures-io's traits with the `futures-io` feature.
+
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use pin_project_lite::pin_project;
+
+use crate::core::Checksum;
+
+pin_project! {
+    /// Wraps an `AsyncWrite` and checksums every byte the inner writer
+    /// accepted.
+    #[derive(Clone, Debug)]
+    pub struct AsyncCrcWriter<W, D> {
+        #[pin]
+        inner: W,
+        digest: D,
+    }
+}
+
+impl<W, D: Default> AsyncCrcWriter<W, D> {
+    /// Wrap `inner` with a fresh digest.
+    pub fn new(inner: W) -> Self {
+        Self::with_digest(inner, D::default())
+    }
+}
+
+impl<W, D> AsyncCrcWriter<W, D> {
+    /// Wrap `inner`, continuing from the state of `digest`.
+    pub fn with_digest(inner: W, digest: D) -> Self {
+        Self { inner, digest }
+    }
+
+    /// Borrow the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Mutably borrow the inner writer.
+    ///
+    /// Bytes written directly to it are not checksummed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Pin-project to the inner writer.
+    ///
+    /// Bytes written directly to it are not checksummed.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut W> {
+        self.project().inner
+    }
+
+    /// Borrow the digest.
+    pub fn digest(&self) -> &D {
+        &self.digest
+    }
+
+    /// Unwrap the inner writer, discarding the digest.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Unwrap the inner writer and the digest.
+    pub fn into_parts(self) -> (W, D) {
+        (self.inner, self.digest)
+    }
+}
+
+impl<W, D: Checksum> AsyncCrcWriter<W, D> {
+    /// Return the CRC of the bytes written so far.
+    pub fn checksum(&self) -> D::Value {
+        self.digest.checksum()
+    }
+}
+
+pin_project! {
+    /// Wraps an `AsyncRead` and checksums every byte read from the inner
+    /// reader.
+    #[derive(Clone, Debug)]
+    pub struct AsyncCrcReader<R, D> {
+        #[pin]
+        inner: R,
+        digest: D,
+    }
+}
+
+impl<R, D: Default> AsyncCrcReader<R, D> {
+    /// Wrap `inner` with a fresh digest.
+    pub fn new(inner: R) -> Self {
+        Self::with_digest(inner, D::default())
+    }
+}
+
+impl<R, D> AsyncCrcReader<R, D> {
+    /// Wrap `inner`, continuing from the state of `digest`.
+    pub fn with_digest(inner: R, digest: D) -> Self {
+        Self { inner, digest }
+    }
+
+    /// Borrow the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Mutably borrow the inner reader.
+    ///
+    /// Bytes read directly from it are not checksummed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Pin-project to the inner reader.
+    ///
+    /// Bytes read directly from it are not checksummed.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut R> {
+        self.project().inner
+    }
+
+    /// Borrow the digest.
+    pub fn digest(&self) -> &D {
+        &self.digest
+    }
+
+    /// Unwrap the inner reader, discarding the digest.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Unwrap the inner reader and the digest.
+    pub fn into_parts(self) -> (R, D) {
+        (self.inner, self.digest)
+    }
+}
+
+impl<R, D: Checksum> AsyncCrcReader<R, D> {
+    /// Return the CRC of the bytes read so far.
+    pub fn checksum(&self) -> D::Value {
+        self.digest.checksum()
+    }
+}
+
+impl_adapter_finalize!(AsyncCrcWriter, AsyncCrcReader);
+
+#[cfg(feature = "tokio")]
+impl<W: tokio::io::AsyncWrite, D: Checksum> tokio::io::AsyncWrite for AsyncCrcWriter<W, D> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.project();
+        let written = ready!(this.inner.poll_write(cx, buf))?;
+        this.digest.update(&buf[..written]);
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<R: tokio::io::AsyncRead, D: Checksum> tokio::io::AsyncRead for AsyncCrcReader<R, D> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.project();
+        let filled = buf.filled().len();
+        ready!(this.inner.poll_read(cx, buf))?;
+        this.digest.update(&buf.filled()[filled..]);
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl<W: futures_io::AsyncWrite, D: Checksum> futures_io::AsyncWrite for AsyncCrcWriter<W, D> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.project();
+        let written = ready!(this.inner.poll_write(cx, buf))?;
+        this.digest.update(&buf[..written]);
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_close(cx)
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl<R: futures_io::AsyncRead, D: Checksum> futures_io::AsyncRead for AsyncCrcReader<R, D> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.project();
+        let read = ready!(this.inner.poll_read(cx, buf))?;
+        this.digest.update(&buf[..read]);
+        Poll::Ready(Ok(read))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crc32c, Crc32c, Crc64Xz, CRC64_XZ};
+
+    fn sample_data() -> Vec<u8> {
+        (0..5000u32)
+            .map(|i| (i.wrapping_mul(13) >> 1) as u8)
+            .collect()
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn tokio_adapters_track_transferred_bytes() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let data = sample_data();
+        let mut reader = AsyncCrcReader::<_, Crc32c>::new(&data[..]);
+        let mut head = [0u8; 100];
+        reader.read_exact(&mut head).await.unwrap();
+        assert_eq!(reader.finalize_u32(), crc32c(&data[..100]));
+
+        let mut writer = AsyncCrcWriter::<_, Crc64Xz>::new(Vec::new());
+        writer.write_all(&head).await.unwrap();
+        tokio::io::copy(&mut reader, &mut writer).await.unwrap();
+        writer.shutdown().await.unwrap();
+        assert_eq!(reader.finalize_u32(), crc32c(&data));
+        assert_eq!(writer.finalize_u64(), CRC64_XZ.checksum(&data));
+        assert_eq!(writer.into_inner(), data);
+    }
+
+    #[cfg(feature = "futures-io")]
+    #[test]
+    fn futures_adapters_track_transferred_bytes() {
+        use futures::io::{AsyncReadExt, AsyncWriteExt, Cursor};
+
+        futures::executor::block_on(async {
+            let data = sample_data();
+            let mut reader = AsyncCrcReader::<_, Crc32c>::new(Cursor::new(&data));
+            let mut head = [0u8; 100];
+            reader.read_exact(&mut head).await.unwrap();
+            assert_eq!(reader.finalize_u32(), crc32c(&data[..100]));
+
+            let mut writer = AsyncCrcWriter::<_, Crc64Xz>::new(Vec::new());
+            writer.write_all(&head).await.unwrap();
+            futures::io::copy(&mut reader, &mut writer).await.unwrap();
+            writer.close().await.unwrap();
+            assert_eq!(reader.checksum(), crc32c(&data));
+            assert_eq!(writer.checksum(), CRC64_XZ.checksum(&data));
+            assert_eq!(writer.into_inner(), data);
+        });
+    }
+}
@@ -0,0 +1,405 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Content-defined chunking driven by a rolling CRC32C fingerprint.
+//!
+//! A chunk ends after the first byte, past the minimum size, at which the
+//! CRC32C of the preceding [`WINDOW`] bytes has all bits of the boundary mask
+//! clear, or at the maximum size. Boundaries depend only on nearby content,
+//! so an insertion shifts the chunks around it and leaves later ones intact.
+
+use std::io::{self, Read};
+
+use crate::crc32c::{Crc32c, CRC32C};
+use crate::rolling::RollingCrc32;
+
+/// Number of bytes the rolling fingerprint covers.
+const WINDOW: usize = 64;
+
+/// Size of the read buffer of [`ReadChunks`].
+const READ_BUFFER: usize = 64 * 1024;
+
+/// A content-defined chunk of the input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Chunk {
+    /// Position of the first byte of the chunk in the input.
+    pub offset: u64,
+    /// Length of the chunk in bytes.
+    pub len: usize,
+    /// CRC32C of the chunk's bytes.
+    pub crc: u32,
+}
+
+/// Splits inputs into content-defined chunks.
+///
+/// ```
+/// use fastcrc::{crc32c, Chunker};
+///
+/// let data: Vec<u8> = (0..100_000u32).map(|i| (i * 7919 >> 5) as u8).collect();
+/// let chunker = Chunker::new(2048, 8192, 32768);
+/// let mut end = 0;
+/// for chunk in chunker.chunks(&data) {
+///     assert_eq!(chunk.offset, end as u64);
+///     let bytes = &data[end..end + chunk.len];
+///     assert_eq!(chunk.crc, crc32c(bytes));
+///     end += chunk.len;
+/// }
+/// assert_eq!(end, data.len());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Chunker {
+    min: usize,
+    max: usize,
+    mask: u32,
+}
+
+impl Chunker {
+    /// Create a chunker producing chunks of `min` to `max` bytes, and of
+    /// about `avg` bytes on random input.
+    ///
+    /// Past `min`, boundaries are on average as far apart as the boundary
+    /// mask has values, so the mask gets the number of bits of the power of
+    /// two nearest to `avg - min`; override it with
+    /// [`with_mask`](Self::with_mask).
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `0 < min <= avg <= max`.
+    pub fn new(min: usize, avg: usize, max: usize) -> Self {
+        assert!(
+            0 < min && min <= avg && avg <= max,
+            "chunk sizes must satisfy 0 < min <= avg <= max"
+        );
+        let target = ((avg - min) as u64).clamp(1, 1 << 32);
+        let upper = target.next_power_of_two();
+        let gap = if upper - target <= target - upper / 2 {
+            upper
+        } else {
+            upper / 2
+        };
+        let bits = gap.trailing_zeros();
+        Self {
+            min,
+            max,
+            mask: ((1u64 << bits) - 1) as u32,
+        }
+    }
+
+    /// Use `mask` to detect boundaries: a chunk may end where the fingerprint
+    /// has every bit of `mask` clear.
+    pub fn with_mask(self, mask: u32) -> Self {
+        Self { mask, ..self }
+    }
+
+    /// Iterate over the chunks of `data`.
+    pub fn chunks<'a>(&self, data: &'a [u8]) -> Chunks<'a> {
+        Chunks {
+            scanner: Scanner::new(*self),
+            data,
+            offset: 0,
+        }
+    }
+
+    /// Iterate over the chunks of the bytes read from `reader`.
+    pub fn read_chunks<R: Read>(&self, reader: R) -> ReadChunks<R> {
+        ReadChunks {
+            scanner: Scanner::new(*self),
+            reader,
+            buffer: vec![0; READ_BUFFER].into_boxed_slice(),
+            pos: 0,
+            filled: 0,
+            offset: 0,
+        }
+    }
+}
+
+/// Tracks the boundary search of the chunk being built.
+#[derive(Clone)]
+struct Scanner {
+    chunker: Chunker,
+    rolling: RollingCrc32,
+    digest: Crc32c,
+    len: usize,
+}
+
+impl Scanner {
+    fn new(chunker: Chunker) -> Self {
+        Self {
+            chunker,
+            rolling: RollingCrc32::new(CRC32C, WINDOW),
+            digest: Crc32c::new(),
+            len: 0,
+        }
+    }
+
+    /// Consume bytes of `data` up to the next boundary, returning how many
+    /// were consumed and whether the chunk is complete.
+    fn scan(&mut self, data: &[u8]) -> (usize, bool) {
+        let Chunker { min, max, mask } = self.chunker;
+        // Bytes leaving the window before the minimum size cannot influence
+        // any boundary, so they are skipped.
+        let skip = (min.saturating_sub(WINDOW + self.len)).min(data.len());
+        let mut consumed = skip;
+        let mut complete = false;
+        for &byte in &data[skip..] {
+            consumed += 1;
+            self.rolling.push(byte);
+            let len = self.len + consumed;
+            if len >= max || (len >= min && self.rolling.checksum() & mask == 0) {
+                complete = true;
+                break;
+            }
+        }
+        self.digest.update(&data[..consumed]);
+        self.len += consumed;
+        (consumed, complete)
+    }
+
+    /// Finish the current chunk, which starts at `offset`.
+    fn finish(&mut self, offset: u64) -> Chunk {
+        let chunk = Chunk {
+            offset,
+            len: self.len,
+            crc: std::mem::take(&mut self.digest).finalize_u32(),
+        };
+        self.rolling.reset();
+        self.len = 0;
+        chunk
+    }
+}
+
+/// Iterator over the chunks of a byte slice, created by
+/// [`Chunker::chunks`].
+#[derive(Clone)]
+pub struct Chunks<'a> {
+    scanner: Scanner,
+    data: &'a [u8],
+    offset: u64,
+}
+
+impl Iterator for Chunks<'_> {
+    type Item = Chunk;
+
+    fn next(&mut self) -> Option<Chunk> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let (consumed, _) = self.scanner.scan(self.data);
+        self.data = &self.data[consumed..];
+        let chunk = self.scanner.finish(self.offset);
+        self.offset += consumed as u64;
+        Some(chunk)
+    }
+}
+
+/// Iterator over the chunks of a reader, created by
+/// [`Chunker::read_chunks`].
+pub struct ReadChunks<R> {
+    scanner: Scanner,
+    reader: R,
+    buffer: Box<[u8]>,
+    pos: usize,
+    filled: usize,
+    offset: u64,
+}
+
+impl<R: Read> ReadChunks<R> {
+    /// Read the next chunk, replacing the contents of `out` with its bytes.
+    ///
+    /// Returns `Ok(None)` once the reader is exhausted.
+    pub fn next_chunk(&mut self, out: &mut Vec<u8>) -> io::Result<Option<Chunk>> {
+        out.clear();
+        self.fill_chunk(Some(out))
+    }
+
+    /// Unwrap the inner reader; buffered bytes not yet chunked are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn fill_chunk(&mut self, mut out: Option<&mut Vec<u8>>) -> io::Result<Option<Chunk>> {
+        loop {
+            if self.pos == self.filled {
+                self.filled = match self.reader.read(&mut self.buffer) {
+                    Ok(read) => read,
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(err) => return Err(err),
+                };
+                self.pos = 0;
+                if self.filled == 0 {
+                    break;
+                }
+            }
+            let pending = &self.buffer[self.pos..self.filled];
+            let (consumed, complete) = self.scanner.scan(pending);
+            if let Some(out) = out.as_deref_mut() {
+                out.extend_from_slice(&pending[..consumed]);
+            }
+            self.pos += consumed;
+            if complete {
+                break;
+            }
+        }
+        if self.scanner.len == 0 {
+            return Ok(None);
+        }
+        let chunk = self.scanner.finish(self.offset);
+        self.offset += chunk.len as u64;
+        Ok(Some(chunk))
+    }
+}
+
+impl<R: Read> Iterator for ReadChunks<R> {
+    type Item = io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<io::Result<Chunk>> {
+        self.fill_chunk(None).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crc32c;
+
+    /// Deterministic pseudo-random bytes.
+    fn random_data(len: usize, seed: u64) -> Vec<u8> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 33) as u8
+            })
+            .collect()
+    }
+
+    /// Reader returning at most `limit` bytes per call.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        limit: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.limit).min(self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn chunks_cover_input_within_bounds() {
+        let data = random_data(500_000, 1);
+        let chunker = Chunker::new(1024, 4608, 16384);
+        let chunks: Vec<_> = chunker.chunks(&data).collect();
+        let mut end = 0;
+        for (i, chunk) in chunks.iter().enumerate() {
+            assert_eq!(chunk.offset, end as u64);
+            assert!(chunk.len <= 16384);
+            assert!(chunk.len >= 1024 || i == chunks.len() - 1);
+            assert_eq!(chunk.crc, crc32c(&data[end..end + chunk.len]));
+            end += chunk.len;
+        }
+        assert_eq!(end, data.len());
+
+        let average = data.len() / chunks.len();
+        assert!(
+            (4608 * 3 / 4..=4608 * 5 / 4).contains(&average),
+            "average: {average}"
+        );
+        assert!(chunks.iter().any(|chunk| chunk.len < 16384));
+    }
+
+    #[test]
+    fn reader_matches_slice_chunks() {
+        let data = random_data(200_000, 2);
+        let chunker = Chunker::new(512, 2048, 8192);
+        let expected: Vec<_> = chunker.chunks(&data).collect();
+
+        let reader = Trickle {
+            data: &data,
+            limit: 1000,
+        };
+        let chunks: Vec<_> = chunker
+            .read_chunks(reader)
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(chunks, expected);
+
+        let mut reader = chunker.read_chunks(&data[..]);
+        let mut bytes = Vec::new();
+        for chunk in &expected {
+            assert_eq!(reader.next_chunk(&mut bytes).unwrap(), Some(*chunk));
+            assert_eq!(bytes, &data[chunk.offset as usize..][..chunk.len]);
+        }
+        assert_eq!(reader.next_chunk(&mut bytes).unwrap(), None);
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn boundaries_resynchronize_after_insertion() {
+        let data = random_data(300_000, 3);
+        let mut edited = data.clone();
+        edited.splice(1000..1000, *b"inserted");
+        let chunker = Chunker::new(1024, 4096, 16384);
+
+        let original: Vec<_> = chunker.chunks(&data).map(|chunk| chunk.crc).collect();
+        let shifted: Vec<_> = chunker.chunks(&edited).map(|chunk| chunk.crc).collect();
+        let shared = shifted.iter().filter(|crc| original.contains(crc)).count();
+        assert!(
+            shared + 3 >= original.len(),
+            "{shared} of {}",
+            original.len()
+        );
+    }
+
+    #[test]
+    fn empty_and_tiny_inputs() {
+        let chunker = Chunker::new(64, 256, 1024);
+        assert_eq!(chunker.chunks(b"").next(), None);
+        assert!(chunker.read_chunks(io::empty()).next().is_none());
+        let chunks: Vec<_> = chunker.chunks(b"tiny").collect();
+        assert_eq!(
+            chunks,
+            [Chunk {
+                offset: 0,
+                len: 4,
+                crc: crc32c(b"tiny"),
+            }]
+        );
+    }
+
+    #[test]
+    fn mask_rounds_to_nearest_power_of_two() {
+        for (avg, mask) in [
+            (1024, 0),
+            (1025, 0),
+            (2300, 1023),
+            (2560, 2047),
+            (4608, 4095),
+        ] {
+            let chunker = Chunker::new(1024, avg, 65536);
+            assert_eq!(chunker, chunker.with_mask(mask), "avg: {avg}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk sizes must satisfy 0 < min <= avg <= max")]
+    fn invalid_sizes_are_rejected() {
+        Chunker::new(4096, 1024, 8192);
+    }
+}
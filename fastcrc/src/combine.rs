@@ -0,0 +1,319 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! CRC combination based on the linearity of CRCs over GF(2).
+//!
+//! Feeding `len` bytes into a register multiplies its polynomial by
+//! `x^(8 * len) mod P` before the message contribution is added, so
+//! `crc(A || B)` follows from `crc(A)`, `crc(B)` and `len(B)` once that power
+//! is known. The power is computed by square-and-multiply in O(log len).
+//!
+//! The same linearity updates a CRC after bytes in the middle of a buffer
+//! change: the CRC moves by the CRC of the XOR of old and new bytes, advanced
+//! over the bytes that follow them.
+
+use crate::core::{reflect_bits, width_mask, Algorithm, Word};
+
+macro_rules! impl_combine {
+    ($($word:ty),*) => {$(
+        impl Algorithm<$word> {
+            /// Compute `crc(A || B)` from `crc_a = crc(A)`, `crc_b = crc(B)`
+            /// and the length of `B` in bytes, like zlib's `crc32_combine`.
+            ///
+            /// Runs in O(log `len_b`) time.
+            pub fn combine(&self, crc_a: $word, crc_b: $word, len_b: u64) -> $word {
+                combine(*self, crc_a, crc_b, len_b)
+            }
+
+            /// Update `crc`, the CRC of a buffer of `len` bytes, after the
+            /// bytes `old` at `offset` were overwritten with `new`.
+            ///
+            /// Runs in O(`new.len()` + log `len`) time, independent of the
+            /// untouched bytes.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `old` and `new` differ in length or the changed
+            /// range ends past `len`.
+            pub fn update_range(
+                &self,
+                crc: $word,
+                len: u64,
+                offset: u64,
+                old: &[u8],
+                new: &[u8],
+            ) -> $word {
+                update_range(*self, crc, len, offset, old, new)
+            }
+        }
+    )*};
+}
+
+impl_combine!(u8, u16, u32, u64);
+
+pub(crate) fn combine<W: Word>(params: Algorithm<W>, crc_a: W, crc_b: W, len_b: u64) -> W {
+    let init = params.init & width_mask(params.width);
+    let a = crc_to_poly(crc_a, params) ^ init;
+    let b = crc_to_poly(crc_b, params);
+    let shifted = mul_mod(a, x_pow_bytes(len_b, params), params);
+    poly_to_crc(shifted ^ b, params)
+}
+
+pub(crate) fn update_range<W: Word>(
+    params: Algorithm<W>,
+    crc: W,
+    len: u64,
+    offset: u64,
+    old: &[u8],
+    new: &[u8],
+) -> W {
+    assert_eq!(
+        old.len(),
+        new.len(),
+        "old and new bytes must match in length"
+    );
+    let tail = offset
+        .checked_add(new.len() as u64)
+        .and_then(|end| len.checked_sub(end))
+        .expect("changed range must lie within the buffer");
+
+    let delta = bare_register(params, old.iter().zip(new).map(|(a, b)| a ^ b));
+    let moved = mul_mod(delta, x_pow_bytes(tail, params), params);
+    poly_to_crc(crc_to_poly(crc, params) ^ moved, params)
+}
+
+/// Feed `data` bit by bit into a zero register, returning the register
+/// polynomial without any output transform.
+fn bare_register<W: Word>(params: Algorithm<W>, data: impl Iterator<Item = u8>) -> W {
+    let top = W::ONE << (u32::from(params.width) - 1);
+    let mut register = W::ZERO;
+    for byte in data {
+        let byte = if params.reflect_in {
+            byte.reverse_bits()
+        } else {
+            byte
+        };
+        for bit in (0..8).rev() {
+            if byte >> bit & 1 != 0 {
+                register = register ^ top;
+            }
+            register = mul_x(register, params);
+        }
+    }
+    register
+}
+
+/// Undo the output transform of `params`, recovering the final register as a
+/// polynomial in the RevEng model's non-reflected bit order.
+pub(crate) fn crc_to_poly<W: Word>(crc: W, params: Algorithm<W>) -> W {
+    let value = (crc ^ params.xor_out) & width_mask(params.width);
+    if params.reflect_out {
+        reflect_bits(value, params.width)
+    } else {
+        value
+    }
+}
+
+/// Apply the output transform of `params` to a register polynomial; the
+/// inverse of [`crc_to_poly`].
+pub(crate) fn poly_to_crc<W: Word>(poly: W, params: Algorithm<W>) -> W {
+    let value = if params.reflect_out {
+        reflect_bits(poly, params.width)
+    } else {
+        poly
+    };
+    (value ^ params.xor_out) & width_mask(params.width)
+}
+
+/// Multiply `value` by `x` modulo the generator polynomial.
+fn mul_x<W: Word>(value: W, params: Algorithm<W>) -> W {
+    let mask = width_mask(params.width);
+    let carry = (value >> (u32::from(params.width) - 1)) & W::ONE != W::ZERO;
+    let shifted = (value << 1) & mask;
+    if carry {
+        shifted ^ (params.polynomial & mask)
+    } else {
+        shifted
+    }
+}
+
+/// Multiply two polynomials modulo the generator polynomial.
+pub(crate) fn mul_mod<W: Word>(a: W, b: W, params: Algorithm<W>) -> W {
+    let mut product = W::ZERO;
+    for bit in (0..u32::from(params.width)).rev() {
+        product = mul_x(product, params);
+        if (b >> bit) & W::ONE != W::ZERO {
+            product = product ^ a;
+        }
+    }
+    product
+}
+
+/// Compute `x^(8 * len) mod P`, the operator that advances a register over
+/// `len` zero bytes.
+pub(crate) fn x_pow_bytes<W: Word>(mut len: u64, params: Algorithm<W>) -> W {
+    let mut result = W::ONE;
+    let mut base = W::ONE;
+    for _ in 0..8 {
+        base = mul_x(base, params);
+    }
+    while len != 0 {
+        if len & 1 != 0 {
+            result = mul_mod(result, base, params);
+        }
+        base = mul_mod(base, base, params);
+        len >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Algorithm16, Algorithm32, Algorithm64, Algorithm8, CRC32, CRC32C, CRC64_XZ};
+
+    /// [`Algorithm::checksum`] of the register width under test.
+    type ChecksumFn<W> = fn(&Algorithm<W>, &[u8]) -> W;
+
+    fn check_combine<W: Word>(params: Algorithm<W>, checksum: ChecksumFn<W>) {
+        let data: Vec<u8> = (0..777u32)
+            .map(|i| (i.wrapping_mul(89) >> 2) as u8)
+            .collect();
+        let expected = checksum(&params, &data);
+        for split in [0, 1, 2, 15, 16, 100, 400, data.len() - 1, data.len()] {
+            let (a, b) = data.split_at(split);
+            let combined = combine(
+                params,
+                checksum(&params, a),
+                checksum(&params, b),
+                b.len() as u64,
+            );
+            assert_eq!(combined, expected, "{}: split {split}", params.name);
+        }
+    }
+
+    #[test]
+    fn combine_matches_contiguous_checksum() {
+        check_combine(CRC32, Algorithm32::checksum);
+        check_combine(CRC32C, Algorithm32::checksum);
+        check_combine(CRC64_XZ, Algorithm64::checksum);
+        check_combine(
+            Algorithm32::new("crc32-bzip2", 0x04C11DB7, !0, !0, false, false),
+            Algorithm32::checksum,
+        );
+        check_combine(
+            Algorithm16::new("crc16-riello", 0x1021, 0xB2AA, 0, true, true),
+            Algorithm16::checksum,
+        );
+        check_combine(
+            Algorithm16::with_width("crc12-umts", 12, 0x80F, 0, 0, false, true),
+            Algorithm16::checksum,
+        );
+        check_combine(
+            Algorithm32::with_width("crc24-ble", 24, 0x65B, 0x555555, 0, true, true),
+            Algorithm32::checksum,
+        );
+        check_combine(
+            Algorithm8::with_width("crc3-gsm", 3, 0x3, 0, 0x7, false, false),
+            Algorithm8::checksum,
+        );
+        check_combine(
+            Algorithm64::with_width(
+                "crc40-gsm",
+                40,
+                0x0482_0009,
+                0,
+                0xFF_FFFF_FFFF,
+                false,
+                false,
+            ),
+            Algorithm64::checksum,
+        );
+    }
+
+    #[test]
+    fn combine_public_api() {
+        let (a, b) = (b"The quick brown fox ", b"jumps over the lazy dog");
+        let whole = b"The quick brown fox jumps over the lazy dog";
+        let crc_a = CRC32.checksum(a);
+        let crc_b = CRC32.checksum(b);
+        assert_eq!(
+            CRC32.combine(crc_a, crc_b, b.len() as u64),
+            CRC32.checksum(whole)
+        );
+    }
+
+    fn check_update_range<W: Word>(params: Algorithm<W>, checksum: ChecksumFn<W>) {
+        let data: Vec<u8> = (0..1000u32)
+            .map(|i| (i.wrapping_mul(53) >> 3) as u8)
+            .collect();
+        let crc = checksum(&params, &data);
+        for (offset, size) in [(0, 1), (0, 16), (123, 7), (500, 200), (996, 4), (400, 0)] {
+            let mut edited = data.clone();
+            for (i, byte) in edited[offset..offset + size].iter_mut().enumerate() {
+                *byte ^= (i as u8).wrapping_mul(37) | 1;
+            }
+            let updated = update_range(
+                params,
+                crc,
+                data.len() as u64,
+                offset as u64,
+                &data[offset..offset + size],
+                &edited[offset..offset + size],
+            );
+            assert_eq!(
+                updated,
+                checksum(&params, &edited),
+                "{}: {offset}+{size}",
+                params.name
+            );
+        }
+    }
+
+    #[test]
+    fn update_range_matches_recomputation() {
+        check_update_range(CRC32, Algorithm32::checksum);
+        check_update_range(CRC32C, Algorithm32::checksum);
+        check_update_range(CRC64_XZ, Algorithm64::checksum);
+        check_update_range(
+            Algorithm32::new("crc32-bzip2", 0x04C11DB7, !0, !0, false, false),
+            Algorithm32::checksum,
+        );
+        check_update_range(
+            Algorithm16::with_width("crc12-umts", 12, 0x80F, 0, 0, false, true),
+            Algorithm16::checksum,
+        );
+        check_update_range(
+            Algorithm8::with_width("crc5-usb", 5, 0x05, 0x1F, 0x1F, true, true),
+            Algorithm8::checksum,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "changed range must lie within the buffer")]
+    fn update_range_rejects_range_past_end() {
+        CRC32.update_range(0, 4, 2, b"abc", b"xyz");
+    }
+
+    #[test]
+    fn x_pow_matches_repeated_multiplication() {
+        let mut expected = 1u32;
+        for len in 0..40u64 {
+            assert_eq!(x_pow_bytes(len, CRC32), expected, "len: {len}");
+            for _ in 0..8 {
+                expected = mul_x(expected, CRC32);
+            }
+        }
+    }
+}
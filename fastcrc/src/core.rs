@@ -13,72 +13,475 @@
 // limitations under the License.
 
 use core::fmt;
+use core::ops::{BitAnd, BitXor, Shl, Shr};
 
+#[cfg(feature = "digest")]
 use digest::core_api::OutputSizeUser;
-use digest::typenum::U4;
+#[cfg(feature = "digest")]
+use digest::typenum::{U1, U2, U4, U8};
+#[cfg(feature = "digest")]
 use digest::{FixedOutput, FixedOutputReset, Output, Reset, Update};
 
-/// Describes a CRC32 variant.
+/// Describes a CRC variant using the Rocksoft model parameters found in the
+/// RevEng CRC Catalogue.
+///
+/// The register type `W` bounds the CRC width; use the [`Algorithm8`],
+/// [`Algorithm16`], [`Algorithm32`] and [`Algorithm64`] aliases to name a
+/// concrete family. CRCs narrower than their register, such as CRC-15/CAN in
+/// an [`Algorithm16`], are built with `with_width`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub(crate) struct Algorithm32 {
+pub struct Algorithm<W> {
     /// Human friendly name (used for debug output or registry keys).
     pub name: &'static str,
+    /// CRC width in bits; only the low `width` bits of the polynomial, `init`
+    /// and `xor_out` are used.
+    pub width: u8,
     /// Standard (non-reflected) polynomial without the top bit.
-    pub polynomial: u32,
+    pub polynomial: W,
     /// Initial register value.
-    pub init: u32,
+    pub init: W,
     /// Final XOR mask applied after the optional reflection step.
-    pub xor_out: u32,
+    pub xor_out: W,
     /// Whether input bytes are processed in reflected form.
     pub reflect_in: bool,
     /// Whether the final CRC value is reflected before `xor_out` is applied.
     pub reflect_out: bool,
 }
 
-impl Algorithm32 {
-    /// Construct a new CRC32 algorithm description.
-    pub const fn new(
-        name: &'static str,
-        polynomial: u32,
-        init: u32,
-        xor_out: u32,
-        reflect_in: bool,
-        reflect_out: bool,
-    ) -> Self {
-        Self {
-            name,
-            polynomial,
-            init,
-            xor_out,
-            reflect_in,
-            reflect_out,
+/// Describes a CRC8 variant.
+pub type Algorithm8 = Algorithm<u8>;
+
+/// Describes a CRC16 variant.
+pub type Algorithm16 = Algorithm<u16>;
+
+/// Describes a CRC32 variant.
+pub type Algorithm32 = Algorithm<u32>;
+
+/// Describes a CRC64 variant.
+pub type Algorithm64 = Algorithm<u64>;
+
+/// Streaming CRC digests that can report the CRC of the bytes fed so far
+/// without being consumed.
+///
+/// This is what lets wrappers such as `CrcWriter` work with every fastcrc
+/// digest regardless of its width, and without the `digest` feature.
+pub trait Checksum {
+    /// Register type the CRC is reported in.
+    type Value: Copy;
+
+    /// Feed `data` into the digest.
+    fn update(&mut self, data: &[u8]);
+
+    /// Reset the digest to its initial value.
+    fn reset(&mut self);
+
+    /// Return the CRC of the bytes fed so far.
+    fn checksum(&self) -> Self::Value;
+}
+
+/// Unsigned integer used as the CRC register of an engine.
+pub(crate) trait Word:
+    Copy
+    + Eq
+    + fmt::Debug
+    + fmt::LowerHex
+    + BitAnd<Output = Self>
+    + BitXor<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+    + 'static
+{
+    /// Register width in bits.
+    const BITS: u32;
+    const ZERO: Self;
+    const ONE: Self;
+    const MAX: Self;
+
+    /// Hardware backend consulted before the table engine.
+    type Accel: Accelerator<Self>;
+
+    fn low_byte(self) -> u8;
+
+    fn reverse_bits(self) -> Self;
+
+    /// Write the register in big-endian byte order into `out`.
+    #[cfg(feature = "digest")]
+    fn write_be_bytes(self, out: &mut [u8]);
+
+    /// Static tables of every built-in algorithm of this register width.
+    #[cfg(feature = "std")]
+    const BUILTIN: &'static [fn() -> &'static CrcTables<Self>];
+
+    /// Build the lookup tables for `params` at runtime.
+    fn tables(params: &Algorithm<Self>) -> CrcTables<Self>;
+}
+
+macro_rules! impl_word {
+    ($word:ty, $output:ty, $accel:ty, $builtin:path) => {
+        impl Algorithm<$word> {
+            /// Construct a new CRC algorithm description spanning the whole
+            /// register.
+            pub const fn new(
+                name: &'static str,
+                polynomial: $word,
+                init: $word,
+                xor_out: $word,
+                reflect_in: bool,
+                reflect_out: bool,
+            ) -> Self {
+                Self::with_width(
+                    name,
+                    <$word>::BITS as u8,
+                    polynomial,
+                    init,
+                    xor_out,
+                    reflect_in,
+                    reflect_out,
+                )
+            }
+
+            /// Construct a new CRC algorithm description for a CRC of `width`
+            /// bits.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `width` is zero or exceeds the register width.
+            pub const fn with_width(
+                name: &'static str,
+                width: u8,
+                polynomial: $word,
+                init: $word,
+                xor_out: $word,
+                reflect_in: bool,
+                reflect_out: bool,
+            ) -> Self {
+                assert!(
+                    width >= 1 && width as u32 <= <$word>::BITS,
+                    "CRC width must fit the register"
+                );
+                Self {
+                    name,
+                    width,
+                    polynomial,
+                    init,
+                    xor_out,
+                    reflect_in,
+                    reflect_out,
+                }
+            }
+
+            /// Compute the CRC of `data` in one shot.
+            ///
+            /// With `std`, parameters matching a built-in algorithm run on its
+            /// static tables; any others build their tables on every call.
+            pub fn checksum(&self, data: &[u8]) -> $word {
+                checksum(*self, data)
+            }
+
+            /// Compute the CRC of `data` in a const context, e.g. to derive
+            /// protocol magic values or match arm patterns at compile time.
+            ///
+            /// This processes one byte at a time and rebuilds its lookup
+            /// table on every call. At runtime, [`checksum`](Self::checksum)
+            /// is as fast for custom parameters and, with `std`, much faster
+            /// for built-in algorithms, whose static tables it reuses.
+            pub const fn checksum_const(&self, data: &[u8]) -> $word {
+                let table = self.table();
+                let pad = <$word>::BITS - self.width as u32;
+                let mask = <$word>::MAX >> pad;
+                let mut state = self.register_init();
+                let mut i = 0;
+                while i < data.len() {
+                    state = if self.reflect_in {
+                        let idx = (state as u8 ^ data[i]) as usize;
+                        ((state as u128) >> 8) as $word ^ table[idx]
+                    } else {
+                        let idx = ((state >> (<$word>::BITS - 8)) as u8 ^ data[i]) as usize;
+                        ((state as u128) << 8) as $word ^ table[idx]
+                    };
+                    i += 1;
+                }
+                let mut crc = if self.reflect_in { state } else { state >> pad };
+                if self.reflect_in != self.reflect_out {
+                    crc = crc.reverse_bits() >> pad;
+                }
+                (crc ^ self.xor_out) & mask
+            }
+
+            /// Build the lookup tables and hardware constants shared by every
+            /// engine computing this algorithm.
+            ///
+            /// Built-in digests evaluate this into a `static`, so creating
+            /// one never rebuilds its tables.
+            pub(crate) const fn tables(&self) -> CrcTables<$word> {
+                CrcTables {
+                    params: *self,
+                    init: self.register_init(),
+                    slices: self.slice_tables(),
+                    accel: <$accel>::new(self),
+                }
+            }
+
+            /// Convert `init` into the register layout described on
+            /// [`CrcEngine`].
+            pub(crate) const fn register_init(&self) -> $word {
+                let pad = <$word>::BITS - self.width as u32;
+                let init = self.init & (<$word>::MAX >> pad);
+                if self.reflect_in {
+                    init.reverse_bits() >> pad
+                } else {
+                    init << pad
+                }
+            }
+
+            /// Build the byte-at-a-time table in the register layout described
+            /// on [`CrcEngine`].
+            pub(crate) const fn table(&self) -> [$word; 256] {
+                let pad = <$word>::BITS - self.width as u32;
+                let polynomial = self.polynomial & (<$word>::MAX >> pad);
+                let mut table = [0; 256];
+                let mut i = 0;
+                while i < 256 {
+                    let mut crc: $word;
+                    let mut bit = 0;
+                    if self.reflect_in {
+                        let reflected = polynomial.reverse_bits() >> pad;
+                        crc = i as $word;
+                        while bit < 8 {
+                            crc = if crc & 1 != 0 {
+                                (crc >> 1) ^ reflected
+                            } else {
+                                crc >> 1
+                            };
+                            bit += 1;
+                        }
+                    } else {
+                        let aligned = polynomial << pad;
+                        let top_bit = 1 << (<$word>::BITS - 1);
+                        crc = (i as $word) << (<$word>::BITS - 8);
+                        while bit < 8 {
+                            crc = if crc & top_bit != 0 {
+                                (crc << 1) ^ aligned
+                            } else {
+                                crc << 1
+                            };
+                            bit += 1;
+                        }
+                    }
+                    table[i] = crc;
+                    i += 1;
+                }
+                table
+            }
+
+            /// Build the `N` lookup tables used by the slicing-by-N update
+            /// loops.
+            ///
+            /// The first table is the classic byte-at-a-time table; every
+            /// following table extends the previous one by feeding an
+            /// additional zero byte.
+            pub(crate) const fn slice_tables<const N: usize>(&self) -> [[$word; 256]; N] {
+                let mut tables = [[0; 256]; N];
+                tables[0] = self.table();
+                let mut k = 1;
+                while k < N {
+                    let mut i = 0;
+                    while i < 256 {
+                        let prev = tables[k - 1][i];
+                        tables[k][i] = if self.reflect_in {
+                            ((prev as u128) >> 8) as $word ^ tables[0][prev as u8 as usize]
+                        } else {
+                            let high = (prev >> (<$word>::BITS - 8)) as usize;
+                            ((prev as u128) << 8) as $word ^ tables[0][high]
+                        };
+                        i += 1;
+                    }
+                    k += 1;
+                }
+                tables
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl OutputSizeUser for CrcEngine<'_, $word> {
+            type OutputSize = $output;
+        }
+
+        impl Word for $word {
+            const BITS: u32 = <$word>::BITS;
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const MAX: Self = <$word>::MAX;
+
+            type Accel = $accel;
+
+            #[cfg(feature = "std")]
+            const BUILTIN: &'static [fn() -> &'static CrcTables<Self>] = $builtin;
+
+            fn low_byte(self) -> u8 {
+                self as u8
+            }
+
+            fn reverse_bits(self) -> Self {
+                <$word>::reverse_bits(self)
+            }
+
+            #[cfg(feature = "digest")]
+            fn write_be_bytes(self, out: &mut [u8]) {
+                out.copy_from_slice(&self.to_be_bytes());
+            }
+
+            fn tables(params: &Algorithm<Self>) -> CrcTables<Self> {
+                params.tables()
+            }
         }
+    };
+}
+
+impl_word!(u8, U1, NoAccel, crate::crc8::BUILTIN_TABLES);
+impl_word!(u16, U2, NoAccel, crate::crc16::BUILTIN_TABLES);
+impl_word!(u32, U4, Accel32, crate::crc32::BUILTIN_TABLES);
+impl_word!(u64, U8, NoAccel, crate::crc64::BUILTIN_TABLES);
+
+/// Optional hardware backend that an engine consults before its table loop.
+pub(crate) trait Accelerator<W>: Copy + Send + Sync {
+    /// Feed as much of `data` as the backend supports into `state`, returning
+    /// the new register and the unprocessed tail.
+    fn update<'a>(&self, state: W, data: &'a [u8]) -> (W, &'a [u8]);
+}
+
+/// Backend for registers without hardware support; leaves all input to the
+/// table engine.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct NoAccel;
+
+impl NoAccel {
+    pub(crate) const fn new<W>(_: &Algorithm<W>) -> Self {
+        NoAccel
+    }
+}
+
+impl<W> Accelerator<W> for NoAccel {
+    fn update<'a>(&self, state: W, data: &'a [u8]) -> (W, &'a [u8]) {
+        (state, data)
     }
 }
 
-/// Streaming CRC32 engine that can host any [`Algorithm32`].
-#[derive(Clone)]
-pub(crate) struct Crc32Engine {
-    params: Algorithm32,
-    table: [u32; 256],
-    state: u32,
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+use crate::simd::Accel32;
+
+#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+type Accel32 = NoAccel;
+
+/// Number of lookup tables used by the slicing-by-N update loop.
+///
+/// Each step consumes `SLICES` input bytes with `SLICES` independent table
+/// lookups, which keeps the loop free of the byte-to-byte dependency chain of
+/// the classic single-table algorithm.
+pub(crate) const SLICES: usize = 16;
+
+/// Slicing tables of one algorithm.
+pub(crate) type SliceTables<W> = [[W; 256]; SLICES];
+
+/// Everything an engine needs besides its register: the algorithm, its lookup
+/// tables and hardware constants.
+///
+/// Built-in digests keep one in a `static` built at compile time with
+/// `Algorithm::tables`, so their engines are only a register and a reference.
+pub(crate) struct CrcTables<W: Word> {
+    params: Algorithm<W>,
+    init: W,
+    slices: SliceTables<W>,
+    accel: W::Accel,
 }
 
-impl Crc32Engine {
-    /// Build a new CRC32 engine for the provided algorithm description.
-    pub(crate) fn new(params: Algorithm32) -> Self {
+impl<W: Word> CrcTables<W> {
+    /// The algorithm these tables were built for.
+    #[cfg(feature = "std")]
+    pub(crate) fn params(&self) -> Algorithm<W> {
+        self.params
+    }
+}
+
+/// Streaming CRC engine that can host any [`Algorithm`] of its register width.
+///
+/// Reflected algorithms keep the register in its low `width` bits, while
+/// non-reflected ones keep it in the high `width` bits so the table loops can
+/// always consume whole bytes at the register's edge.
+#[derive(Clone, Copy)]
+pub(crate) struct CrcEngine<'t, W: Word> {
+    tables: &'t CrcTables<W>,
+    state: W,
+    len: u64,
+}
+
+/// Serialized form of a digest's resumable state.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "CrcState")]
+pub(crate) struct SerdeState<W> {
+    pub(crate) state: W,
+    pub(crate) bytes_processed: u64,
+}
+
+impl<'t, W: Word> CrcEngine<'t, W> {
+    /// Build a new engine computing the algorithm of `tables`.
+    pub(crate) fn new(tables: &'t CrcTables<W>) -> Self {
         Self {
-            params,
-            table: build_table(params.polynomial, params.reflect_in),
-            state: params.init,
+            tables,
+            state: tables.init,
+            len: 0,
         }
     }
 
+    /// Resume an engine from a register saved by [`CrcEngine::state`] after
+    /// `len` bytes.
+    pub(crate) fn with_state(tables: &'t CrcTables<W>, state: W, len: u64) -> Self {
+        let params = tables.params;
+        let state = state & width_mask(params.width);
+        let state = if params.reflect_in {
+            state
+        } else {
+            state << (W::BITS - u32::from(params.width))
+        };
+        Self { tables, state, len }
+    }
+
+    /// Resume an engine from a CRC returned by [`CrcEngine::finalize`] after
+    /// `len` bytes, undoing the final XOR and reflection.
+    pub(crate) fn with_checksum(tables: &'t CrcTables<W>, crc: W, len: u64) -> Self {
+        let params = tables.params;
+        let mut state = (crc ^ params.xor_out) & width_mask(params.width);
+        if params.reflect_in ^ params.reflect_out {
+            state = reflect_bits(state, params.width);
+        }
+        Self::with_state(tables, state, len)
+    }
+
+    /// The register before the final reflection and XOR, in its low `width`
+    /// bits.
+    pub(crate) fn state(&self) -> W {
+        let params = self.tables.params;
+        if params.reflect_in {
+            self.state
+        } else {
+            self.state >> (W::BITS - u32::from(params.width))
+        }
+    }
+
+    /// Number of bytes fed since the engine was created or reset.
+    pub(crate) fn len(&self) -> u64 {
+        self.len
+    }
+
     fn absorb(&mut self, data: &[u8]) {
-        self.state = if self.params.reflect_in {
-            update_reflected(self.state, &self.table, data)
+        self.len = self.len.wrapping_add(data.len() as u64);
+        let tables = self.tables;
+        let (state, data) = tables.accel.update(self.state, data);
+        self.state = if tables.params.reflect_in {
+            update_reflected_sliced(state, &tables.slices, data)
         } else {
-            update_standard(self.state, &self.table, data)
+            update_standard_sliced(state, &tables.slices, data)
         };
     }
 
@@ -89,120 +492,237 @@ impl Crc32Engine {
 
     /// Reset the digest to its initial value.
     pub(crate) fn reset(&mut self) {
-        self.state = self.params.init;
+        self.state = self.tables.init;
+        self.len = 0;
     }
 
-    /// Retrieve the finalized checksum as `u32`.
-    pub(crate) fn finalize_u32(&self) -> u32 {
-        finalize_value(self.state, self.params)
+    /// Retrieve the finalized checksum.
+    pub(crate) fn finalize(&self) -> W {
+        finalize_value(self.state, self.tables.params)
     }
 }
 
-impl fmt::Debug for Crc32Engine {
+/// Format a digest as its algorithm and current CRC, printed with one hex
+/// digit per four bits of the CRC width.
+pub(crate) fn fmt_digest<W: Word>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    params: &Algorithm<W>,
+    crc: W,
+) -> fmt::Result {
+    let digits = (usize::from(params.width) + 3) / 4;
+    f.debug_struct(name)
+        .field("algorithm", &params.name)
+        .field("checksum", &format_args!("0x{crc:0digits$x}"))
+        .finish()
+}
+
+impl<W: Word> fmt::Debug for CrcEngine<'_, W> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Crc32Engine")
-            .field("algorithm", &self.params.name)
-            .field("state", &format_args!("0x{state:08x}", state = self.state))
+        let digits = (usize::from(self.tables.params.width) + 3) / 4;
+        f.debug_struct("CrcEngine")
+            .field("algorithm", &self.tables.params.name)
+            .field("state", &format_args!("0x{:0digits$x}", self.state()))
             .finish()
     }
 }
 
-impl OutputSizeUser for Crc32Engine {
-    type OutputSize = U4;
-}
-
-impl Update for Crc32Engine {
+#[cfg(feature = "digest")]
+impl<W: Word> Update for CrcEngine<'_, W> {
     fn update(&mut self, data: &[u8]) {
         self.absorb(data);
     }
 }
 
-impl FixedOutput for Crc32Engine {
+#[cfg(feature = "digest")]
+impl<W: Word> FixedOutput for CrcEngine<'_, W>
+where
+    Self: OutputSizeUser,
+{
     fn finalize_into(self, out: &mut Output<Self>) {
-        out.copy_from_slice(&self.finalize_u32().to_be_bytes());
+        self.finalize().write_be_bytes(out);
     }
 }
 
-impl FixedOutputReset for Crc32Engine {
+#[cfg(feature = "digest")]
+impl<W: Word> FixedOutputReset for CrcEngine<'_, W>
+where
+    Self: OutputSizeUser,
+{
     fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
-        out.copy_from_slice(&self.finalize_u32().to_be_bytes());
+        self.finalize().write_be_bytes(out);
         self.reset();
     }
 }
 
-impl Reset for Crc32Engine {
+#[cfg(feature = "digest")]
+impl<W: Word> Reset for CrcEngine<'_, W> {
     fn reset(&mut self) {
-        Crc32Engine::reset(self);
+        CrcEngine::reset(self);
     }
 }
 
-fn finalize_value(state: u32, params: Algorithm32) -> u32 {
-    let mut crc = state;
-    if params.reflect_in ^ params.reflect_out {
-        crc = reflect_bits(crc, 32);
+/// Compute the CRC of `data` in one shot.
+pub(crate) fn checksum<W: Word>(params: Algorithm<W>, data: &[u8]) -> W {
+    with_tables(params, |tables| checksum_with(tables, data))
+}
+
+/// Compute the CRC of `data` in one shot on prebuilt `tables`.
+pub(crate) fn checksum_with<W: Word>(tables: &CrcTables<W>, data: &[u8]) -> W {
+    let mut engine = CrcEngine::new(tables);
+    engine.update(data);
+    engine.finalize()
+}
+
+/// Run `f` on the static tables of the built-in algorithm with the parameters
+/// of `params`, whatever its name, or else on tables built for this call.
+pub(crate) fn with_tables<W: Word, R>(
+    params: Algorithm<W>,
+    f: impl FnOnce(&CrcTables<W>) -> R,
+) -> R {
+    match builtin_tables(&params) {
+        Some(tables) => f(tables),
+        None => f(&W::tables(&params)),
     }
-    crc ^ params.xor_out
 }
 
-fn update_reflected(mut state: u32, table: &[u32; 256], data: &[u8]) -> u32 {
-    for &byte in data {
-        let idx = ((state as u8) ^ byte) as usize;
-        state = (state >> 8) ^ table[idx];
+/// Find the static tables of a built-in algorithm with the parameters of
+/// `params`.
+///
+/// Only `std` builds look: listing the built-in tables links every one of
+/// them into the binary, which small targets cannot afford.
+#[cfg(feature = "std")]
+fn builtin_tables<W: Word>(params: &Algorithm<W>) -> Option<&'static CrcTables<W>> {
+    W::BUILTIN.iter().map(|tables| tables()).find(|tables| {
+        tables.params
+            == Algorithm {
+                name: tables.params.name,
+                ..*params
+            }
+    })
+}
+
+#[cfg(not(feature = "std"))]
+fn builtin_tables<W: Word>(_: &Algorithm<W>) -> Option<&'static CrcTables<W>> {
+    None
+}
+
+/// Mask selecting the low `width` bits of a register.
+pub(crate) fn width_mask<W: Word>(width: u8) -> W {
+    W::MAX >> (W::BITS - u32::from(width))
+}
+
+pub(crate) fn finalize_value<W: Word>(state: W, params: Algorithm<W>) -> W {
+    let mut crc = if params.reflect_in {
+        state
+    } else {
+        state >> (W::BITS - u32::from(params.width))
+    };
+    if params.reflect_in ^ params.reflect_out {
+        crc = reflect_bits(crc, params.width);
     }
-    state
+    (crc ^ params.xor_out) & width_mask(params.width)
 }
 
-fn update_standard(mut state: u32, table: &[u32; 256], data: &[u8]) -> u32 {
-    for &byte in data {
-        let idx = (((state >> 24) as u8) ^ byte) as usize;
-        state = (state << 8) ^ table[idx];
+/// Shift the register one byte towards its low end, dropping the low byte.
+fn shr_byte<W: Word>(value: W) -> W {
+    if W::BITS > 8 {
+        value >> 8
+    } else {
+        W::ZERO
     }
-    state
 }
 
-fn build_table(polynomial: u32, reflect: bool) -> [u32; 256] {
-    let mut table = [0u32; 256];
-    if reflect {
-        let reflected = reflect_bits(polynomial, 32);
-        for (i, slot) in table.iter_mut().enumerate() {
-            let mut crc = i as u32;
-            for _ in 0..8 {
-                if (crc & 1) != 0 {
-                    crc = (crc >> 1) ^ reflected;
-                } else {
-                    crc >>= 1;
-                }
-            }
-            *slot = crc;
-        }
+/// Shift the register one byte towards its high end, dropping the high byte.
+fn shl_byte<W: Word>(value: W) -> W {
+    if W::BITS > 8 {
+        value << 8
     } else {
-        for (i, slot) in table.iter_mut().enumerate() {
-            let mut crc = (i as u32) << 24;
-            for _ in 0..8 {
-                if (crc & 0x8000_0000) != 0 {
-                    crc = (crc << 1) ^ polynomial;
-                } else {
-                    crc <<= 1;
-                }
-            }
-            *slot = crc;
+        W::ZERO
+    }
+}
+
+/// Return the byte of the register that is consumed next by a non-reflected
+/// update.
+fn high_byte<W: Word>(value: W) -> u8 {
+    (value >> (W::BITS - 8)).low_byte()
+}
+
+/// Slicing-by-N update for reflected algorithms.
+///
+/// `tables[k][b]` holds the CRC of byte `b` followed by `k` zero bytes, so a
+/// block of `N` bytes can be folded into the register with one lookup per byte.
+fn update_reflected_sliced<W: Word, const N: usize>(
+    mut state: W,
+    tables: &[[W; 256]; N],
+    data: &[u8],
+) -> W {
+    let register_bytes = (W::BITS / 8) as usize;
+    let mut blocks = data.chunks_exact(N);
+    for block in &mut blocks {
+        let mut crc = W::ZERO;
+        for (i, &byte) in block.iter().enumerate() {
+            let byte = if i < register_bytes {
+                byte ^ (state >> (8 * i as u32)).low_byte()
+            } else {
+                byte
+            };
+            crc = crc ^ tables[N - 1 - i][byte as usize];
         }
+        state = crc;
     }
-    table
+    update_reflected(state, &tables[0], blocks.remainder())
 }
 
-fn reflect_bits(mut value: u32, width: u8) -> u32 {
-    let mut reversed = 0u32;
-    let mut i = 0;
-    while i < width {
-        reversed <<= 1;
-        if (value & 1) != 0 {
-            reversed |= 1;
+/// Slicing-by-N update for non-reflected algorithms.
+///
+/// Mirrors [`update_reflected_sliced`] with the register consumed MSB first.
+fn update_standard_sliced<W: Word, const N: usize>(
+    mut state: W,
+    tables: &[[W; 256]; N],
+    data: &[u8],
+) -> W {
+    let register_bytes = (W::BITS / 8) as usize;
+    let mut blocks = data.chunks_exact(N);
+    for block in &mut blocks {
+        let mut crc = W::ZERO;
+        for (i, &byte) in block.iter().enumerate() {
+            let byte = if i < register_bytes {
+                byte ^ high_byte(state << (8 * i as u32))
+            } else {
+                byte
+            };
+            crc = crc ^ tables[N - 1 - i][byte as usize];
         }
-        value >>= 1;
-        i += 1;
+        state = crc;
+    }
+    update_standard(state, &tables[0], blocks.remainder())
+}
+
+fn update_reflected<W: Word>(state: W, table: &[W; 256], data: &[u8]) -> W {
+    data.iter()
+        .fold(state, |state, &byte| update_byte(state, table, true, byte))
+}
+
+fn update_standard<W: Word>(state: W, table: &[W; 256], data: &[u8]) -> W {
+    data.iter()
+        .fold(state, |state, &byte| update_byte(state, table, false, byte))
+}
+
+/// Feed one byte into a register in the layout described on [`CrcEngine`],
+/// using the single 256-entry `table` of the algorithm.
+#[inline]
+pub(crate) fn update_byte<W: Word>(state: W, table: &[W; 256], reflected: bool, byte: u8) -> W {
+    if reflected {
+        shr_byte(state) ^ table[(state.low_byte() ^ byte) as usize]
+    } else {
+        shl_byte(state) ^ table[(high_byte(state) ^ byte) as usize]
     }
-    reversed
+}
+
+/// Reverse the low `width` bits of `value`, discarding any bits above them.
+pub(crate) fn reflect_bits<W: Word>(value: W, width: u8) -> W {
+    value.reverse_bits() >> (W::BITS - u32::from(width))
 }
 
 #[cfg(test)]
@@ -214,14 +734,359 @@ mod tests {
 
     #[test]
     fn reflect_roundtrip() {
-        assert_eq!(reflect_bits(0b1001, 4), 0b1001);
-        assert_eq!(reflect_bits(0b0011, 4), 0b1100);
+        assert_eq!(reflect_bits(0b1001u32, 4), 0b1001);
+        assert_eq!(reflect_bits(0b0011u32, 4), 0b1100);
+    }
+
+    fn sample_data() -> Vec<u8> {
+        (0..1031u32)
+            .map(|i| (i.wrapping_mul(31) >> 3) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn sliced_updates_match_bytewise() {
+        let data = sample_data();
+        for reflect in [true, false] {
+            let params = Algorithm32::new("test", IEEE.polynomial, 0, 0, reflect, reflect);
+            let table = params.table();
+            let tables8 = params.slice_tables::<8>();
+            let tables16 = params.slice_tables::<16>();
+            for len in [0, 1, 7, 8, 15, 16, 17, 100, data.len()] {
+                let input = &data[..len];
+                let (expected, by8, by16) = if reflect {
+                    (
+                        update_reflected(0x1234_5678, &table, input),
+                        update_reflected_sliced(0x1234_5678, &tables8, input),
+                        update_reflected_sliced(0x1234_5678, &tables16, input),
+                    )
+                } else {
+                    (
+                        update_standard(0x1234_5678, &table, input),
+                        update_standard_sliced(0x1234_5678, &tables8, input),
+                        update_standard_sliced(0x1234_5678, &tables16, input),
+                    )
+                };
+                assert_eq!(by8, expected, "reflect: {reflect}, len: {len}");
+                assert_eq!(by16, expected, "reflect: {reflect}, len: {len}");
+            }
+        }
+    }
+
+    fn check_sliced_matches_bytewise<W: Word>(polynomial: W) {
+        let data = sample_data();
+        for reflect in [true, false] {
+            let params = Algorithm {
+                name: "test",
+                width: W::BITS as u8,
+                polynomial,
+                init: W::ZERO,
+                xor_out: W::ZERO,
+                reflect_in: reflect,
+                reflect_out: reflect,
+            };
+            let tables = W::tables(&params).slices;
+            let table = tables[0];
+            for len in [0, 1, 15, 16, 17, 100, data.len()] {
+                let input = &data[..len];
+                let init = polynomial ^ W::ONE;
+                let (expected, sliced) = if reflect {
+                    (
+                        update_reflected(init, &table, input),
+                        update_reflected_sliced(init, &tables, input),
+                    )
+                } else {
+                    (
+                        update_standard(init, &table, input),
+                        update_standard_sliced(init, &tables, input),
+                    )
+                };
+                assert_eq!(sliced, expected, "reflect: {reflect}, len: {len}");
+            }
+        }
+    }
+
+    #[test]
+    fn sliced_updates_match_bytewise_other_widths() {
+        check_sliced_matches_bytewise(0x31u8);
+        check_sliced_matches_bytewise(0x1021u16);
+        check_sliced_matches_bytewise(0x42F0_E1EB_A9EA_3693u64);
+    }
+
+    #[test]
+    fn non_reflected_engine_matches_known_checksum() {
+        // CRC-32/BZIP2 from the RevEng CRC Catalogue.
+        let bzip2 = Algorithm32::new(
+            "crc32-bzip2",
+            0x04C11DB7,
+            0xFFFF_FFFF,
+            0xFFFF_FFFF,
+            false,
+            false,
+        );
+        let tables = bzip2.tables();
+        let mut engine = CrcEngine::new(&tables);
+        engine.update(b"123456789");
+        assert_eq!(engine.finalize(), 0xFC89_1918);
+    }
+
+    fn check_value<W: Word>(params: Algorithm<W>, expected: W) {
+        let tables = W::tables(&params);
+        let mut engine = CrcEngine::new(&tables);
+        engine.update(b"123456789");
+        assert_eq!(engine.finalize(), expected, "{}", params.name);
+
+        // Exercise the slicing loop as well as the bytewise tail.
+        let data = sample_data();
+        let mut streamed = CrcEngine::new(&tables);
+        for chunk in data.chunks(37) {
+            streamed.update(chunk);
+        }
+        let mut one_shot = CrcEngine::new(&tables);
+        one_shot.update(&data);
+        assert_eq!(streamed.finalize(), one_shot.finalize(), "{}", params.name);
+    }
+
+    /// Check values of odd-width algorithms sourced from the RevEng CRC
+    /// Catalogue.
+    #[test]
+    fn odd_width_check_values() {
+        check_value(
+            Algorithm8::with_width("crc3-gsm", 3, 0x3, 0x0, 0x7, false, false),
+            0x4,
+        );
+        check_value(
+            Algorithm8::with_width("crc5-usb", 5, 0x05, 0x1F, 0x1F, true, true),
+            0x19,
+        );
+        check_value(
+            Algorithm8::with_width("crc6-g-704", 6, 0x03, 0x0, 0x0, true, true),
+            0x06,
+        );
+        check_value(
+            Algorithm8::with_width("crc7-mmc", 7, 0x09, 0x0, 0x0, false, false),
+            0x75,
+        );
+        check_value(
+            Algorithm8::with_width("crc7-rohc", 7, 0x4F, 0x7F, 0x0, true, true),
+            0x53,
+        );
+        check_value(
+            Algorithm16::with_width("crc10-atm", 10, 0x233, 0, 0, false, false),
+            0x199,
+        );
+        check_value(
+            Algorithm16::with_width("crc11-flexray", 11, 0x385, 0x01A, 0, false, false),
+            0x5A3,
+        );
+        check_value(
+            Algorithm16::with_width("crc12-dect", 12, 0x80F, 0, 0, false, false),
+            0xF5B,
+        );
+        check_value(
+            Algorithm16::with_width("crc12-umts", 12, 0x80F, 0, 0, false, true),
+            0xDAF,
+        );
+        check_value(
+            Algorithm16::with_width("crc15-can", 15, 0x4599, 0, 0, false, false),
+            0x059E,
+        );
+        check_value(
+            Algorithm16::new("crc16-riello", 0x1021, 0xB2AA, 0, true, true),
+            0x63D0,
+        );
+        check_value(
+            Algorithm32::with_width("crc17-can-fd", 17, 0x1685B, 0, 0, false, false),
+            0x04F03,
+        );
+        check_value(
+            Algorithm32::with_width("crc21-can-fd", 21, 0x102899, 0, 0, false, false),
+            0x0ED841,
+        );
+        check_value(
+            Algorithm32::with_width("crc24-ble", 24, 0x00065B, 0x555555, 0, true, true),
+            0xC25A56,
+        );
+        check_value(
+            Algorithm32::with_width("crc24-openpgp", 24, 0x864CFB, 0xB704CE, 0, false, false),
+            0x21CF02,
+        );
+        check_value(
+            Algorithm32::with_width(
+                "crc31-philips",
+                31,
+                0x04C11DB7,
+                0x7FFF_FFFF,
+                0x7FFF_FFFF,
+                false,
+                false,
+            ),
+            0x0CE9_E46C,
+        );
+        check_value(
+            Algorithm64::with_width(
+                "crc40-gsm",
+                40,
+                0x00_0482_0009,
+                0,
+                0xFF_FFFF_FFFF,
+                false,
+                false,
+            ),
+            0xD4_164F_C646,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "CRC width must fit the register")]
+    fn oversized_width_is_rejected() {
+        Algorithm16::with_width("too-wide", 17, 0x1021, 0, 0, false, false);
+    }
+
+    #[test]
+    fn debug_shows_state_in_low_bits() {
+        let umts = Algorithm16::with_width("crc12-umts", 12, 0x80F, 0, 0, false, true);
+        let tables = umts.tables();
+        let mut engine = CrcEngine::new(&tables);
+        engine.update(b"123456789");
+        assert_eq!(
+            format!("{engine:?}"),
+            r#"CrcEngine { algorithm: "crc12-umts", state: 0xf5b }"#
+        );
     }
 
     #[test]
     fn dyn_engine_matches_known_checksum() {
-        let mut engine = Crc32Engine::new(IEEE);
+        let tables = IEEE.tables();
+        let mut engine = CrcEngine::new(&tables);
         engine.update(b"123456789");
-        assert_eq!(engine.finalize_u32(), 0xCBF4_3926);
+        assert_eq!(engine.finalize(), 0xCBF4_3926);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn checksum_reuses_builtin_tables() {
+        let castagnoli = Algorithm32 {
+            name: "castagnoli",
+            ..crate::CRC32C
+        };
+        let tables = builtin_tables(&castagnoli).unwrap();
+        assert!(core::ptr::eq(tables, crate::Crc32c::tables()));
+        assert!(builtin_tables(&Algorithm32 {
+            init: 0,
+            ..castagnoli
+        })
+        .is_none());
+    }
+
+    #[test]
+    fn const_checksum_matches_runtime() {
+        const CHECK: u32 = IEEE.checksum_const(b"123456789");
+        assert_eq!(CHECK, 0xCBF4_3926);
+
+        let data = sample_data();
+        let algorithms32 = [
+            IEEE,
+            Algorithm32::new("bzip2", 0x04C11DB7, 0xFFFF_FFFF, 0xFFFF_FFFF, false, false),
+            Algorithm32::with_width("crc-24/ble", 24, 0x65B, 0x555555, 0, true, true),
+            Algorithm32::with_width(
+                "crc-31/philips",
+                31,
+                0x04C11DB7,
+                0x7FFF_FFFF,
+                0x7FFF_FFFF,
+                false,
+                false,
+            ),
+        ];
+        for params in algorithms32 {
+            for len in [0, 1, 9, 100, data.len()] {
+                let input = &data[..len];
+                assert_eq!(
+                    params.checksum_const(input),
+                    params.checksum(input),
+                    "{}",
+                    params.name
+                );
+            }
+        }
+        let crc5 = Algorithm8::with_width("crc-5/usb", 5, 0x05, 0x1F, 0x1F, true, true);
+        assert_eq!(crc5.checksum_const(b"123456789"), 0x19);
+        let crc12 = Algorithm16::with_width("crc-12/umts", 12, 0x80F, 0, 0, false, true);
+        assert_eq!(crc12.checksum_const(b"123456789"), 0xDAF);
+        let ecma = Algorithm64::new("crc-64/ecma-182", 0x42F0_E1EB_A9EA_3693, 0, 0, false, false);
+        assert_eq!(ecma.checksum_const(&data), ecma.checksum(&data));
+    }
+
+    #[test]
+    fn static_tables_match_runtime_tables() {
+        static TABLES: CrcTables<u32> = IEEE.tables();
+        let mut engine = CrcEngine::new(&TABLES);
+        let mut copy = engine;
+        engine.update(b"123456789");
+        assert_eq!(engine.finalize(), 0xCBF4_3926);
+        assert_eq!(copy.finalize(), IEEE.checksum(b""));
+        copy.update(b"123456789");
+        assert_eq!(copy.finalize(), 0xCBF4_3926);
+        assert!(
+            core::mem::size_of::<CrcEngine<'static, u32>>()
+                <= 2 * core::mem::size_of::<usize>() + 8
+        );
+    }
+
+    fn check_resume_from_checksum<W: Word>(params: Algorithm<W>) {
+        let tables = W::tables(&params);
+        let data = sample_data();
+        for split in [0, 1, 9, 100, data.len()] {
+            let (head, tail) = data.split_at(split);
+            let mut engine = CrcEngine::with_checksum(&tables, checksum(params, head), 0);
+            engine.update(tail);
+            assert_eq!(
+                engine.finalize(),
+                checksum(params, &data),
+                "{}",
+                params.name
+            );
+        }
+    }
+
+    #[test]
+    fn engine_resumes_from_checksum() {
+        check_resume_from_checksum(IEEE);
+        check_resume_from_checksum(Algorithm8::with_width(
+            "crc-5/usb",
+            5,
+            0x05,
+            0x1F,
+            0x1F,
+            true,
+            true,
+        ));
+        check_resume_from_checksum(Algorithm16::with_width(
+            "crc-12/umts",
+            12,
+            0x80F,
+            0,
+            0,
+            false,
+            true,
+        ));
+        check_resume_from_checksum(Algorithm32::with_width(
+            "crc-24/ble",
+            24,
+            0x65B,
+            0x555555,
+            0,
+            true,
+            true,
+        ));
+        check_resume_from_checksum(Algorithm64::new(
+            "crc-64/we",
+            0x42F0_E1EB_A9EA_3693,
+            u64::MAX,
+            u64::MAX,
+            false,
+            false,
+        ));
     }
 }
@@ -0,0 +1,195 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "digest")]
+use digest::typenum::U2;
+
+use crate::core::Algorithm16;
+#[cfg(feature = "std")]
+use crate::core::CrcTables;
+
+/// CRC-16/MODBUS, used by Modbus RTU serial links.
+pub const CRC16_MODBUS: Algorithm16 =
+    Algorithm16::new("crc16-modbus", 0x8005, 0xFFFF, 0x0000, true, true);
+
+/// CRC-16/IBM-SDLC (a.k.a. X-25, ISO-HDLC), used by X.25 and HDLC frames.
+#[doc(alias = "CRC16_X25")]
+pub const CRC16_IBM_SDLC: Algorithm16 =
+    Algorithm16::new("crc16-ibm-sdlc", 0x1021, 0xFFFF, 0xFFFF, true, true);
+
+/// CRC-16/XMODEM (a.k.a. ZMODEM, ACORN), used by the XMODEM file transfer protocol.
+pub const CRC16_XMODEM: Algorithm16 =
+    Algorithm16::new("crc16-xmodem", 0x1021, 0x0000, 0x0000, false, false);
+
+/// CRC-16/KERMIT (a.k.a. CCITT, CCITT-TRUE), used by the Kermit protocol and Bluetooth.
+pub const CRC16_KERMIT: Algorithm16 =
+    Algorithm16::new("crc16-kermit", 0x1021, 0x0000, 0x0000, true, true);
+
+/// CRC-16/IBM-3740 (a.k.a. CCITT-FALSE, AUTOSAR), used by floppy disk formats.
+#[doc(alias = "CRC16_CCITT_FALSE")]
+pub const CRC16_IBM_3740: Algorithm16 =
+    Algorithm16::new("crc16-ibm-3740", 0x1021, 0xFFFF, 0x0000, false, false);
+
+/// CRC-16/ARC (a.k.a. IBM, LHA), used by the ARC and LHA archivers.
+pub const CRC16_ARC: Algorithm16 =
+    Algorithm16::new("crc16-arc", 0x8005, 0x0000, 0x0000, true, true);
+
+/// CRC-16/USB, protecting USB data packets.
+pub const CRC16_USB: Algorithm16 =
+    Algorithm16::new("crc16-usb", 0x8005, 0xFFFF, 0xFFFF, true, true);
+
+/// CRC-16/T10-DIF, used by SCSI data integrity fields.
+pub const CRC16_T10_DIF: Algorithm16 =
+    Algorithm16::new("crc16-t10-dif", 0x8BB7, 0x0000, 0x0000, false, false);
+
+/// CRC-16/DNP, used by the DNP3 SCADA protocol.
+pub const CRC16_DNP: Algorithm16 =
+    Algorithm16::new("crc16-dnp", 0x3D65, 0x0000, 0xFFFF, true, true);
+
+/// CRC-16/GENIBUS (a.k.a. DARC, EPC, I-CODE), used by RFID tags and Grundfos GENIbus.
+pub const CRC16_GENIBUS: Algorithm16 =
+    Algorithm16::new("crc16-genibus", 0x1021, 0xFFFF, 0xFFFF, false, false);
+
+define_digest!(
+    /// CRC-16/MODBUS digest.
+    Crc16Modbus, crc16_modbus, CRC16_MODBUS, u16, U2, finalize_u16, "CRC-16/MODBUS"
+);
+
+define_digest!(
+    /// CRC-16/IBM-SDLC digest.
+    Crc16IbmSdlc, crc16_ibm_sdlc, CRC16_IBM_SDLC, u16, U2, finalize_u16, "CRC-16/IBM-SDLC"
+);
+
+define_digest!(
+    /// CRC-16/XMODEM digest.
+    Crc16Xmodem, crc16_xmodem, CRC16_XMODEM, u16, U2, finalize_u16, "CRC-16/XMODEM"
+);
+
+define_digest!(
+    /// CRC-16/KERMIT digest.
+    Crc16Kermit, crc16_kermit, CRC16_KERMIT, u16, U2, finalize_u16, "CRC-16/KERMIT"
+);
+
+define_digest!(
+    /// CRC-16/IBM-3740 digest.
+    Crc16Ibm3740, crc16_ibm_3740, CRC16_IBM_3740, u16, U2, finalize_u16, "CRC-16/IBM-3740"
+);
+
+define_digest!(
+    /// CRC-16/ARC digest.
+    Crc16Arc, crc16_arc, CRC16_ARC, u16, U2, finalize_u16, "CRC-16/ARC"
+);
+
+define_digest!(
+    /// CRC-16/USB digest.
+    Crc16Usb, crc16_usb, CRC16_USB, u16, U2, finalize_u16, "CRC-16/USB"
+);
+
+define_digest!(
+    /// CRC-16/T10-DIF digest.
+    Crc16T10Dif, crc16_t10_dif, CRC16_T10_DIF, u16, U2, finalize_u16, "CRC-16/T10-DIF"
+);
+
+define_digest!(
+    /// CRC-16/DNP digest.
+    Crc16Dnp, crc16_dnp, CRC16_DNP, u16, U2, finalize_u16, "CRC-16/DNP"
+);
+
+define_digest!(
+    /// CRC-16/GENIBUS digest.
+    Crc16Genibus, crc16_genibus, CRC16_GENIBUS, u16, U2, finalize_u16, "CRC-16/GENIBUS"
+);
+
+/// Tables of every built-in CRC16 algorithm, which [`Algorithm16::checksum`]
+/// reuses instead of building its own.
+#[cfg(feature = "std")]
+pub(crate) static BUILTIN_TABLES: &[fn() -> &'static CrcTables<u16>] = &[
+    Crc16Modbus::tables,
+    Crc16IbmSdlc::tables,
+    Crc16Xmodem::tables,
+    Crc16Kermit::tables,
+    Crc16Ibm3740::tables,
+    Crc16Arc::tables,
+    Crc16Usb::tables,
+    Crc16T10Dif::tables,
+    Crc16Dnp::tables,
+    Crc16Genibus::tables,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Checksum16 = fn(&[u8]) -> u16;
+
+    /// Check values sourced from the RevEng CRC Catalogue
+    /// (http://reveng.sourceforge.net/crc-catalogue/) which is distributed
+    /// as public domain data.
+    const CHECK_VECTORS: &[(Checksum16, u16)] = &[
+        (crc16_modbus, 0x4B37),
+        (crc16_ibm_sdlc, 0x906E),
+        (crc16_xmodem, 0x31C3),
+        (crc16_kermit, 0x2189),
+        (crc16_ibm_3740, 0x29B1),
+        (crc16_arc, 0xBB3D),
+        (crc16_usb, 0xB4C8),
+        (crc16_t10_dif, 0xD0DB),
+        (crc16_dnp, 0xEA82),
+        (crc16_genibus, 0xD64E),
+    ];
+
+    #[test]
+    fn crc16_check_values() {
+        for (i, &(checksum, expected)) in CHECK_VECTORS.iter().enumerate() {
+            assert_eq!(checksum(b"123456789"), expected, "vector: {i}");
+        }
+    }
+
+    #[test]
+    fn crc16_known_values() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(crc16_modbus(data), 0xA89C);
+        assert_eq!(crc16_xmodem(data), 0xF0C8);
+    }
+
+    #[test]
+    fn crc16_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32)
+            .map(|i| (i.wrapping_mul(7) >> 1) as u8)
+            .collect();
+        let mut modbus = Crc16Modbus::new();
+        let mut xmodem = Crc16Xmodem::new();
+        for chunk in data.chunks(11) {
+            modbus.update(chunk);
+            xmodem.update(chunk);
+        }
+        assert_eq!(modbus.finalize_u16(), crc16_modbus(&data));
+        assert_eq!(xmodem.finalize_u16(), crc16_xmodem(&data));
+    }
+
+    #[test]
+    fn crc16_resumes_from_saved_state() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let (head, tail) = data.split_at(17);
+        let mut xmodem = Crc16Xmodem::new();
+        xmodem.update(head);
+        let (state, len) = (xmodem.state(), xmodem.bytes_processed());
+        assert_eq!(len, head.len() as u64);
+
+        let mut resumed = Crc16Xmodem::from_state(state, len);
+        resumed.update(tail);
+        assert_eq!(resumed.bytes_processed(), data.len() as u64);
+        assert_eq!(resumed.finalize_u16(), crc16_xmodem(data));
+    }
+}
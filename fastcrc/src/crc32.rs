@@ -12,78 +12,217 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use digest::core_api::OutputSizeUser;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+#[cfg(feature = "digest")]
 use digest::typenum::U4;
-use digest::{FixedOutput, FixedOutputReset, HashMarker, Output, Reset, Update};
 
-use crate::core::{Algorithm32, Crc32Engine};
+use crate::core::Algorithm32;
+#[cfg(feature = "std")]
+use crate::core::CrcTables;
 
 /// Classic Ethernet CRC32 (a.k.a. IEEE, ISO-HDLC).
-pub(crate) const CRC32: Algorithm32 =
+pub const CRC32: Algorithm32 =
     Algorithm32::new("crc32", 0x04C11DB7, 0xFFFF_FFFF, 0xFFFF_FFFF, true, true);
 
-/// CRC32 digest implementing the RustCrypto [`digest::Digest`] blanket impl.
-#[derive(Clone)]
-pub struct Crc32 {
-    inner: Crc32Engine,
-}
+/// CRC-32/BZIP2 (a.k.a. AAL5, DECT-B), used by bzip2 and ATM AAL5.
+pub const CRC32_BZIP2: Algorithm32 = Algorithm32::new(
+    "crc32-bzip2",
+    0x04C11DB7,
+    0xFFFF_FFFF,
+    0xFFFF_FFFF,
+    false,
+    false,
+);
+
+/// CRC-32/MPEG-2, used by MPEG-2 transport stream tables.
+pub const CRC32_MPEG_2: Algorithm32 = Algorithm32::new(
+    "crc32-mpeg-2",
+    0x04C11DB7,
+    0xFFFF_FFFF,
+    0x0000_0000,
+    false,
+    false,
+);
+
+/// CRC-32/CKSUM (a.k.a. POSIX), the core of the POSIX `cksum` utility.
+///
+/// `cksum` additionally feeds the input length before finalizing; this
+/// constant is the bare algorithm as listed in the catalogue.
+#[doc(alias = "CRC32_POSIX")]
+pub const CRC32_CKSUM: Algorithm32 = Algorithm32::new(
+    "crc32-cksum",
+    0x04C11DB7,
+    0x0000_0000,
+    0xFFFF_FFFF,
+    false,
+    false,
+);
+
+/// CRC-32/JAMCRC, the bitwise complement of [`CRC32`] used by Altera tools.
+pub const CRC32_JAMCRC: Algorithm32 = Algorithm32::new(
+    "crc32-jamcrc",
+    0x04C11DB7,
+    0xFFFF_FFFF,
+    0x0000_0000,
+    true,
+    true,
+);
+
+/// CRC-32/XFER, used by the XFER file transfer protocol.
+pub const CRC32_XFER: Algorithm32 = Algorithm32::new(
+    "crc32-xfer",
+    0x0000_00AF,
+    0x0000_0000,
+    0x0000_0000,
+    false,
+    false,
+);
+
+/// CRC-32/AUTOSAR, the 32-bit profile of AUTOSAR end-to-end protection.
+pub const CRC32_AUTOSAR: Algorithm32 = Algorithm32::new(
+    "crc32-autosar",
+    0xF4ACFB13,
+    0xFFFF_FFFF,
+    0xFFFF_FFFF,
+    true,
+    true,
+);
 
+/// CRC-32/AIXM (a.k.a. CRC-32Q), used by the Aeronautical Information
+/// Exchange Model.
+#[doc(alias = "CRC32_Q")]
+pub const CRC32_AIXM: Algorithm32 = Algorithm32::new(
+    "crc32-aixm",
+    0x814141AB,
+    0x0000_0000,
+    0x0000_0000,
+    false,
+    false,
+);
+
+/// CRC-32/BASE91-D (a.k.a. CRC-32D), used by the BASE91 encoding.
+#[doc(alias = "CRC32_D")]
+pub const CRC32_BASE91_D: Algorithm32 = Algorithm32::new(
+    "crc32-base91-d",
+    0xA833982B,
+    0xFFFF_FFFF,
+    0xFFFF_FFFF,
+    true,
+    true,
+);
+
+/// CRC-32/CD-ROM-EDC, the error detection code of CD-ROM mode 1 sectors.
+pub const CRC32_CD_ROM_EDC: Algorithm32 = Algorithm32::new(
+    "crc32-cd-rom-edc",
+    0x8001801B,
+    0x0000_0000,
+    0x0000_0000,
+    true,
+    true,
+);
+
+/// CRC-32/MEF, used by the Metro Ethernet Forum.
+pub const CRC32_MEF: Algorithm32 = Algorithm32::new(
+    "crc32-mef",
+    0x741B8CD7,
+    0xFFFF_FFFF,
+    0x0000_0000,
+    true,
+    true,
+);
+
+define_digest!(
+    /// CRC32 digest.
+    Crc32, crc32, CRC32, u32, U4, finalize_u32, "CRC32"
+);
+
+impl_hasher!(Crc32, Crc32BuildHasher, "CRC32");
+
+#[cfg(feature = "std")]
 impl Crc32 {
-    /// Create a new CRC32 (IEEE) digest instance.
-    pub fn new() -> Self {
-        Self {
-            inner: Crc32Engine::new(CRC32),
-        }
+    /// Compute the CRC32 of `data` using up to `threads` threads; returns the
+    /// same value as [`crc32`].
+    pub fn checksum_parallel(data: &[u8], threads: usize) -> u32 {
+        CRC32.checksum_parallel(data, threads)
     }
 
-    /// Retrieve the checksum as `u32`.
-    pub fn finalize_u32(self) -> u32 {
-        self.inner.finalize_u32()
+    /// Compute the CRC32 of the file at `path` using up to `threads` threads.
+    pub fn checksum_file_parallel<P: AsRef<Path>>(path: P, threads: usize) -> io::Result<u32> {
+        CRC32.checksum_file_parallel(path, threads)
     }
 }
 
-impl Default for Crc32 {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+define_digest!(
+    /// CRC-32/BZIP2 digest.
+    Crc32Bzip2, crc32_bzip2, CRC32_BZIP2, u32, U4, finalize_u32, "CRC-32/BZIP2"
+);
 
-impl OutputSizeUser for Crc32 {
-    type OutputSize = U4;
-}
+define_digest!(
+    /// CRC-32/MPEG-2 digest.
+    Crc32Mpeg2, crc32_mpeg_2, CRC32_MPEG_2, u32, U4, finalize_u32, "CRC-32/MPEG-2"
+);
 
-impl Update for Crc32 {
-    fn update(&mut self, data: &[u8]) {
-        self.inner.update(data);
-    }
-}
+define_digest!(
+    /// CRC-32/CKSUM digest.
+    Crc32Cksum, crc32_cksum, CRC32_CKSUM, u32, U4, finalize_u32, "CRC-32/CKSUM"
+);
 
-impl Reset for Crc32 {
-    fn reset(&mut self) {
-        self.inner.reset();
-    }
-}
+define_digest!(
+    /// CRC-32/JAMCRC digest.
+    Crc32Jamcrc, crc32_jamcrc, CRC32_JAMCRC, u32, U4, finalize_u32, "CRC-32/JAMCRC"
+);
 
-impl FixedOutput for Crc32 {
-    fn finalize_into(self, out: &mut Output<Self>) {
-        self.inner.finalize_into(out);
-    }
-}
+define_digest!(
+    /// CRC-32/XFER digest.
+    Crc32Xfer, crc32_xfer, CRC32_XFER, u32, U4, finalize_u32, "CRC-32/XFER"
+);
 
-impl FixedOutputReset for Crc32 {
-    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
-        self.inner.finalize_into_reset(out);
-    }
-}
+define_digest!(
+    /// CRC-32/AUTOSAR digest.
+    Crc32Autosar, crc32_autosar, CRC32_AUTOSAR, u32, U4, finalize_u32, "CRC-32/AUTOSAR"
+);
 
-impl HashMarker for Crc32 {}
+define_digest!(
+    /// CRC-32/AIXM digest.
+    Crc32Aixm, crc32_aixm, CRC32_AIXM, u32, U4, finalize_u32, "CRC-32/AIXM"
+);
 
-/// One-shot helper for calculating IEEE CRC32 over a byte slice.
-pub fn crc32(data: &[u8]) -> u32 {
-    let mut digest = Crc32::new();
-    digest.update(data);
-    digest.finalize_u32()
-}
+define_digest!(
+    /// CRC-32/BASE91-D digest.
+    Crc32Base91D, crc32_base91_d, CRC32_BASE91_D, u32, U4, finalize_u32, "CRC-32/BASE91-D"
+);
+
+define_digest!(
+    /// CRC-32/CD-ROM-EDC digest.
+    Crc32CdRomEdc, crc32_cd_rom_edc, CRC32_CD_ROM_EDC, u32, U4, finalize_u32, "CRC-32/CD-ROM-EDC"
+);
+
+define_digest!(
+    /// CRC-32/MEF digest.
+    Crc32Mef, crc32_mef, CRC32_MEF, u32, U4, finalize_u32, "CRC-32/MEF"
+);
+
+/// Tables of every built-in CRC32 algorithm, which [`Algorithm32::checksum`]
+/// reuses instead of building its own.
+#[cfg(feature = "std")]
+pub(crate) static BUILTIN_TABLES: &[fn() -> &'static CrcTables<u32>] = &[
+    Crc32::tables,
+    crate::crc32c::Crc32c::tables,
+    Crc32Bzip2::tables,
+    Crc32Mpeg2::tables,
+    Crc32Cksum::tables,
+    Crc32Jamcrc::tables,
+    Crc32Xfer::tables,
+    Crc32Autosar::tables,
+    Crc32Aixm::tables,
+    Crc32Base91D::tables,
+    Crc32CdRomEdc::tables,
+    Crc32Mef::tables,
+];
 
 #[cfg(test)]
 mod tests {
@@ -98,6 +237,51 @@ mod tests {
         (b"The quick brown fox jumps over the lazy dog", 0x414F_A339),
     ];
 
+    type Checksum32 = fn(&[u8]) -> u32;
+
+    /// Check and residue values from the RevEng CRC Catalogue.
+    const CATALOGUE_VECTORS: &[(Algorithm32, Checksum32, u32, u32)] = &[
+        (CRC32, crc32, 0xCBF4_3926, 0xDEBB_20E3),
+        (CRC32_BZIP2, crc32_bzip2, 0xFC89_1918, 0xC704_DD7B),
+        (CRC32_MPEG_2, crc32_mpeg_2, 0x0376_E6E7, 0x0000_0000),
+        (CRC32_CKSUM, crc32_cksum, 0x765E_7680, 0xC704_DD7B),
+        (CRC32_JAMCRC, crc32_jamcrc, 0x340B_C6D9, 0x0000_0000),
+        (CRC32_XFER, crc32_xfer, 0xBD0B_E338, 0x0000_0000),
+        (CRC32_AUTOSAR, crc32_autosar, 0x1697_D06A, 0x904C_DDBF),
+        (CRC32_AIXM, crc32_aixm, 0x3010_BF7F, 0x0000_0000),
+        (CRC32_BASE91_D, crc32_base91_d, 0x8731_5576, 0x4527_0551),
+        (CRC32_CD_ROM_EDC, crc32_cd_rom_edc, 0x6EC2_EDC4, 0x0000_0000),
+        (CRC32_MEF, crc32_mef, 0xD2C2_2F51, 0x0000_0000),
+    ];
+
+    #[test]
+    fn crc32_catalogue_check_values() {
+        for &(params, checksum, check, _) in CATALOGUE_VECTORS {
+            assert_eq!(checksum(b"123456789"), check, "{}", params.name);
+            assert_eq!(params.checksum(b"123456789"), check, "{}", params.name);
+        }
+    }
+
+    #[test]
+    fn crc32_catalogue_residues() {
+        for &(params, checksum, check, residue) in CATALOGUE_VECTORS {
+            // Append the CRC in the order its bits leave the register to form
+            // a codeword; the register then holds the residue.
+            let mut codeword = b"123456789".to_vec();
+            if params.reflect_out {
+                codeword.extend_from_slice(&check.to_le_bytes());
+            } else {
+                codeword.extend_from_slice(&check.to_be_bytes());
+            }
+            assert_eq!(
+                checksum(&codeword) ^ params.xor_out,
+                residue,
+                "{}",
+                params.name
+            );
+        }
+    }
+
     #[test]
     fn crc32_known_value() {
         for &(input, expected) in IEEE_VECTORS {
@@ -114,4 +298,38 @@ mod tests {
         }
         assert_eq!(digest.finalize_u32(), crc32(data));
     }
+
+    #[test]
+    fn crc32_combine_matches_one_shot() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let (a, b) = data.split_at(16);
+        let combined = Crc32::combine(crc32(a), crc32(b), b.len() as u64);
+        assert_eq!(combined, crc32(data));
+    }
+
+    #[test]
+    fn crc32_hasher_widens_checksum() {
+        use core::hash::{BuildHasher, Hasher};
+
+        let mut hasher = Crc32BuildHasher::default().build_hasher();
+        hasher.write(b"1234");
+        hasher.write(b"56789");
+        let hash = hasher.finish();
+        assert_eq!(hash as u32, crc32(b"123456789"));
+        assert_eq!((hash >> 32) as u32, crc32(b"123456789"));
+
+        let mut map = std::collections::HashMap::with_hasher(Crc32BuildHasher::default());
+        map.insert("shard-1", 1);
+        map.insert("shard-2", 2);
+        assert_eq!(map["shard-2"], 2);
+    }
+
+    #[test]
+    fn crc32_appends_to_existing_checksum() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let (object, appended) = data.split_at(10);
+        let mut digest = Crc32::from_checksum(crc32(object), object.len() as u64);
+        digest.update(appended);
+        assert_eq!(digest.finalize_u32(), crc32(data));
+    }
 }
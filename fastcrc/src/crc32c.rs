@@ -12,79 +12,41 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use digest::core_api::OutputSizeUser;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+#[cfg(feature = "digest")]
 use digest::typenum::U4;
-use digest::{FixedOutput, FixedOutputReset, HashMarker, Output, Reset, Update};
 
-use crate::core::{Algorithm32, Crc32Engine};
+use crate::core::Algorithm32;
 
 /// Castagnoli CRC32 (CRC32C) widely used by SSE4.2 instructions, NVMe, etc.
-pub(crate) const CRC32C: Algorithm32 =
+pub const CRC32C: Algorithm32 =
     Algorithm32::new("crc32c", 0x1EDC6F41, 0xFFFF_FFFF, 0xFFFF_FFFF, true, true);
 
-/// CRC32C digest implementing the RustCrypto [`digest::Digest`] blanket impl.
-#[derive(Clone)]
-pub struct Crc32c {
-    inner: Crc32Engine,
-}
-
-impl Crc32c {
-    /// Create a new CRC32C digest instance.
-    pub fn new() -> Self {
-        Self {
-            inner: Crc32Engine::new(CRC32C),
-        }
-    }
-
-    /// Retrieve the checksum as `u32`.
-    pub fn finalize_u32(self) -> u32 {
-        self.inner.finalize_u32()
-    }
-}
+define_digest!(
+    /// CRC32C digest.
+    Crc32c, crc32c, CRC32C, u32, U4, finalize_u32, "CRC32C"
+);
 
-impl Default for Crc32c {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+impl_hasher!(Crc32c, Crc32cBuildHasher, "CRC32C");
 
-impl OutputSizeUser for Crc32c {
-    type OutputSize = U4;
-}
-
-impl Update for Crc32c {
-    fn update(&mut self, data: &[u8]) {
-        self.inner.update(data);
-    }
-}
-
-impl Reset for Crc32c {
-    fn reset(&mut self) {
-        self.inner.reset();
-    }
-}
-
-impl FixedOutput for Crc32c {
-    fn finalize_into(self, out: &mut Output<Self>) {
-        self.inner.finalize_into(out);
+#[cfg(feature = "std")]
+impl Crc32c {
+    /// Compute the CRC32C of `data` using up to `threads` threads; returns the
+    /// same value as [`crc32c`].
+    pub fn checksum_parallel(data: &[u8], threads: usize) -> u32 {
+        CRC32C.checksum_parallel(data, threads)
     }
-}
 
-impl FixedOutputReset for Crc32c {
-    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
-        self.inner.finalize_into_reset(out);
+    /// Compute the CRC32C of the file at `path` using up to `threads` threads.
+    pub fn checksum_file_parallel<P: AsRef<Path>>(path: P, threads: usize) -> io::Result<u32> {
+        CRC32C.checksum_file_parallel(path, threads)
     }
 }
 
-impl HashMarker for Crc32c {}
-
-/// One-shot helper for calculating Castagnoli CRC32 over a byte slice.
-pub fn crc32c(data: &[u8]) -> u32 {
-    let mut digest = Crc32c::new();
-    digest.update(data);
-    digest.finalize_u32()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -114,4 +76,73 @@ mod tests {
         }
         assert_eq!(digest.finalize_u32(), crc32c(data));
     }
+
+    #[test]
+    fn crc32c_combine_matches_one_shot() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let (a, b) = data.split_at(16);
+        let combined = Crc32c::combine(crc32c(a), crc32c(b), b.len() as u64);
+        assert_eq!(combined, crc32c(data));
+    }
+
+    #[test]
+    fn crc32c_hasher_widens_checksum() {
+        use core::hash::{BuildHasher, Hasher};
+
+        let mut hasher = Crc32cBuildHasher::default().build_hasher();
+        hasher.write(b"1234");
+        hasher.write(b"56789");
+        let hash = hasher.finish();
+        assert_eq!(hash as u32, crc32c(b"123456789"));
+        assert_eq!((hash >> 32) as u32, crc32c(b"123456789"));
+
+        let mut map = std::collections::HashMap::with_hasher(Crc32cBuildHasher::default());
+        map.insert("shard-1", 1);
+        map.insert("shard-2", 2);
+        assert_eq!(map["shard-2"], 2);
+    }
+
+    #[test]
+    fn crc32c_resumes_from_saved_state() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let (head, tail) = data.split_at(20);
+        let mut digest = Crc32c::new();
+        digest.update(head);
+        let mut resumed = Crc32c::from_state(digest.state(), digest.bytes_processed());
+        resumed.update(tail);
+        assert_eq!(resumed.bytes_processed(), data.len() as u64);
+        assert_eq!(resumed.finalize_u32(), crc32c(data));
+
+        digest.reset();
+        assert_eq!(digest.bytes_processed(), 0);
+        assert_eq!(digest.state(), Crc32c::new().state());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn crc32c_state_roundtrips_through_serde() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let (head, tail) = data.split_at(9);
+        let mut digest = Crc32c::new();
+        digest.update(head);
+        let saved = serde_json::to_string(&digest).unwrap();
+        assert_eq!(
+            saved,
+            format!(r#"{{"state":{},"bytes_processed":9}}"#, digest.state())
+        );
+
+        let mut resumed: Crc32c = serde_json::from_str(&saved).unwrap();
+        resumed.update(tail);
+        assert_eq!(resumed.finalize_u32(), crc32c(data));
+    }
+
+    #[test]
+    fn crc32c_appends_to_existing_checksum() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let (object, appended) = data.split_at(25);
+        let mut digest = Crc32c::from_checksum(crc32c(object), object.len() as u64);
+        digest.update(appended);
+        assert_eq!(digest.bytes_processed(), data.len() as u64);
+        assert_eq!(digest.finalize_u32(), crc32c(data));
+    }
 }
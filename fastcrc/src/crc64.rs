@@ -0,0 +1,182 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "digest")]
+use digest::typenum::U8;
+
+use crate::core::Algorithm64;
+#[cfg(feature = "std")]
+use crate::core::CrcTables;
+
+/// CRC-64/XZ (a.k.a. CRC-64/GO-ECMA), used by the XZ container format.
+pub const CRC64_XZ: Algorithm64 = Algorithm64::new(
+    "crc64-xz",
+    0x42F0_E1EB_A9EA_3693,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+    true,
+    true,
+);
+
+/// CRC-64/ECMA-182, the unreflected ECMA polynomial with a zero register.
+pub const CRC64_ECMA_182: Algorithm64 = Algorithm64::new(
+    "crc64-ecma-182",
+    0x42F0_E1EB_A9EA_3693,
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+    false,
+    false,
+);
+
+/// CRC-64/GO-ISO, based on the ISO 3309 polynomial.
+pub const CRC64_GO_ISO: Algorithm64 = Algorithm64::new(
+    "crc64-go-iso",
+    0x0000_0000_0000_001B,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+    true,
+    true,
+);
+
+/// CRC-64/NVME, used by NVMe end-to-end data protection.
+pub const CRC64_NVME: Algorithm64 = Algorithm64::new(
+    "crc64-nvme",
+    0xAD93_D235_94C9_3659,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+    true,
+    true,
+);
+
+/// CRC-64/WE, the unreflected ECMA polynomial with an inverted register.
+pub const CRC64_WE: Algorithm64 = Algorithm64::new(
+    "crc64-we",
+    0x42F0_E1EB_A9EA_3693,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+    false,
+    false,
+);
+
+/// CRC-64/REDIS (Jones polynomial), used by Redis RDB dumps.
+pub const CRC64_REDIS: Algorithm64 = Algorithm64::new(
+    "crc64-redis",
+    0xAD93_D235_94C9_35A9,
+    0x0000_0000_0000_0000,
+    0x0000_0000_0000_0000,
+    true,
+    true,
+);
+
+define_digest!(
+    /// CRC-64/XZ digest.
+    Crc64Xz, crc64_xz, CRC64_XZ, u64, U8, finalize_u64, "CRC-64/XZ"
+);
+
+define_digest!(
+    /// CRC-64/ECMA-182 digest.
+    Crc64Ecma182, crc64_ecma_182, CRC64_ECMA_182, u64, U8, finalize_u64, "CRC-64/ECMA-182"
+);
+
+define_digest!(
+    /// CRC-64/GO-ISO digest.
+    Crc64GoIso, crc64_go_iso, CRC64_GO_ISO, u64, U8, finalize_u64, "CRC-64/GO-ISO"
+);
+
+define_digest!(
+    /// CRC-64/NVME digest.
+    Crc64Nvme, crc64_nvme, CRC64_NVME, u64, U8, finalize_u64, "CRC-64/NVME"
+);
+
+define_digest!(
+    /// CRC-64/WE digest.
+    Crc64We, crc64_we, CRC64_WE, u64, U8, finalize_u64, "CRC-64/WE"
+);
+
+define_digest!(
+    /// CRC-64/REDIS digest.
+    Crc64Redis, crc64_redis, CRC64_REDIS, u64, U8, finalize_u64, "CRC-64/REDIS"
+);
+
+/// Tables of every built-in CRC64 algorithm, which [`Algorithm64::checksum`]
+/// reuses instead of building its own.
+#[cfg(feature = "std")]
+pub(crate) static BUILTIN_TABLES: &[fn() -> &'static CrcTables<u64>] = &[
+    Crc64Xz::tables,
+    Crc64Ecma182::tables,
+    Crc64GoIso::tables,
+    Crc64Nvme::tables,
+    Crc64We::tables,
+    Crc64Redis::tables,
+];
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    type Checksum64 = fn(&[u8]) -> u64;
+
+    /// Check values sourced from the RevEng CRC Catalogue
+    /// (http://reveng.sourceforge.net/crc-catalogue/) which is distributed
+    /// as public domain data.
+    const CHECK_VECTORS: &[(Checksum64, u64)] = &[
+        (crc64_xz, 0x995D_C9BB_DF19_39FA),
+        (crc64_ecma_182, 0x6C40_DF5F_0B49_7347),
+        (crc64_go_iso, 0xB909_56C7_75A4_1001),
+        (crc64_nvme, 0xAE8B_1486_0A79_9888),
+        (crc64_we, 0x62EC_59E3_F1A4_F00A),
+        (crc64_redis, 0xE9C6_D914_C4B8_D9CA),
+    ];
+
+    #[test]
+    fn crc64_check_values() {
+        for (i, &(checksum, expected)) in CHECK_VECTORS.iter().enumerate() {
+            assert_eq!(checksum(b"123456789"), expected, "vector: {i}");
+            assert_eq!(checksum(b""), checksum(&[]), "vector: {i}");
+        }
+    }
+
+    #[test]
+    fn crc64_known_values() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(crc64_xz(data), 0x5B5E_B8C2_E54A_A1C4);
+        assert_eq!(crc64_ecma_182(data), 0x41E0_5242_FFA9_883B);
+    }
+
+    #[test]
+    fn crc64_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32)
+            .map(|i| (i.wrapping_mul(7) >> 1) as u8)
+            .collect();
+        let mut xz = Crc64Xz::new();
+        let mut we = Crc64We::new();
+        for chunk in data.chunks(13) {
+            xz.update(chunk);
+            we.update(chunk);
+        }
+        assert_eq!(xz.finalize_u64(), crc64_xz(&data));
+        assert_eq!(we.finalize_u64(), crc64_we(&data));
+    }
+
+    #[test]
+    fn crc64_debug_shows_checksum() {
+        let mut digest = Crc64Xz::new();
+        digest.update(b"123456789");
+        assert_eq!(
+            format!("{digest:?}"),
+            r#"Crc64Xz { algorithm: "crc64-xz", checksum: 0x995dc9bbdf1939fa }"#
+        );
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "digest")]
+use digest::typenum::U1;
+
+use crate::core::Algorithm8;
+#[cfg(feature = "std")]
+use crate::core::CrcTables;
+
+/// CRC-8/SMBUS, the packet error code (PEC) of SMBus transactions.
+pub const CRC8_SMBUS: Algorithm8 = Algorithm8::new("crc8-smbus", 0x07, 0x00, 0x00, false, false);
+
+/// CRC-8/MAXIM-DOW (a.k.a. MAXIM, DOW-CRC), used by Dallas/Maxim 1-Wire devices.
+#[doc(alias = "CRC8_MAXIM")]
+pub const CRC8_MAXIM_DOW: Algorithm8 =
+    Algorithm8::new("crc8-maxim-dow", 0x31, 0x00, 0x00, true, true);
+
+/// CRC-8/AUTOSAR, specified by the AUTOSAR CRC library.
+pub const CRC8_AUTOSAR: Algorithm8 =
+    Algorithm8::new("crc8-autosar", 0x2F, 0xFF, 0xFF, false, false);
+
+/// CRC-8/SAE-J1850, used by the SAE J1850 vehicle bus.
+pub const CRC8_SAE_J1850: Algorithm8 =
+    Algorithm8::new("crc8-sae-j1850", 0x1D, 0xFF, 0xFF, false, false);
+
+/// CRC-8/BLUETOOTH, protecting Bluetooth packet headers.
+pub const CRC8_BLUETOOTH: Algorithm8 =
+    Algorithm8::new("crc8-bluetooth", 0xA7, 0x00, 0x00, true, true);
+
+/// CRC-8/DVB-S2, protecting DVB-S2 baseband frames.
+pub const CRC8_DVB_S2: Algorithm8 = Algorithm8::new("crc8-dvb-s2", 0xD5, 0x00, 0x00, false, false);
+
+define_digest!(
+    /// CRC-8/SMBUS digest.
+    Crc8Smbus, crc8_smbus, CRC8_SMBUS, u8, U1, finalize_u8, "CRC-8/SMBUS"
+);
+
+define_digest!(
+    /// CRC-8/MAXIM-DOW digest.
+    Crc8MaximDow, crc8_maxim_dow, CRC8_MAXIM_DOW, u8, U1, finalize_u8, "CRC-8/MAXIM-DOW"
+);
+
+define_digest!(
+    /// CRC-8/AUTOSAR digest.
+    Crc8Autosar, crc8_autosar, CRC8_AUTOSAR, u8, U1, finalize_u8, "CRC-8/AUTOSAR"
+);
+
+define_digest!(
+    /// CRC-8/SAE-J1850 digest.
+    Crc8SaeJ1850, crc8_sae_j1850, CRC8_SAE_J1850, u8, U1, finalize_u8, "CRC-8/SAE-J1850"
+);
+
+define_digest!(
+    /// CRC-8/BLUETOOTH digest.
+    Crc8Bluetooth, crc8_bluetooth, CRC8_BLUETOOTH, u8, U1, finalize_u8, "CRC-8/BLUETOOTH"
+);
+
+define_digest!(
+    /// CRC-8/DVB-S2 digest.
+    Crc8DvbS2, crc8_dvb_s2, CRC8_DVB_S2, u8, U1, finalize_u8, "CRC-8/DVB-S2"
+);
+
+/// Tables of every built-in CRC8 algorithm, which [`Algorithm8::checksum`]
+/// reuses instead of building its own.
+#[cfg(feature = "std")]
+pub(crate) static BUILTIN_TABLES: &[fn() -> &'static CrcTables<u8>] = &[
+    Crc8Smbus::tables,
+    Crc8MaximDow::tables,
+    Crc8Autosar::tables,
+    Crc8SaeJ1850::tables,
+    Crc8Bluetooth::tables,
+    Crc8DvbS2::tables,
+];
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    type Checksum8 = fn(&[u8]) -> u8;
+
+    /// Check values sourced from the RevEng CRC Catalogue
+    /// (http://reveng.sourceforge.net/crc-catalogue/) which is distributed
+    /// as public domain data.
+    const CHECK_VECTORS: &[(Checksum8, u8)] = &[
+        (crc8_smbus, 0xF4),
+        (crc8_maxim_dow, 0xA1),
+        (crc8_autosar, 0xDF),
+        (crc8_sae_j1850, 0x4B),
+        (crc8_bluetooth, 0x26),
+        (crc8_dvb_s2, 0xBC),
+    ];
+
+    #[test]
+    fn crc8_check_values() {
+        for (i, &(checksum, expected)) in CHECK_VECTORS.iter().enumerate() {
+            assert_eq!(checksum(b"123456789"), expected, "vector: {i}");
+        }
+    }
+
+    #[test]
+    fn crc8_known_values() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(crc8_smbus(data), 0xC1);
+        assert_eq!(crc8_maxim_dow(data), 0x16);
+    }
+
+    #[test]
+    fn crc8_streaming_matches_one_shot() {
+        let data: Vec<u8> = (0..1000u32)
+            .map(|i| (i.wrapping_mul(7) >> 1) as u8)
+            .collect();
+        let mut smbus = Crc8Smbus::new();
+        let mut maxim = Crc8MaximDow::new();
+        for chunk in data.chunks(19) {
+            smbus.update(chunk);
+            maxim.update(chunk);
+        }
+        assert_eq!(smbus.finalize_u8(), crc8_smbus(&data));
+        assert_eq!(maxim.finalize_u8(), crc8_maxim_dow(&data));
+    }
+}
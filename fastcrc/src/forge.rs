@@ -0,0 +1,204 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! CRC forging: choosing four bytes so that a message hits a target CRC.
+//!
+//! Each table step shifts one byte out of the register and XORs in a table
+//! entry. For a 32-bit CRC with a `+ 1` term, the byte of the entry that
+//! lands where the shifted-out byte was is distinct for each of the 256
+//! entries, so the step can be run backwards through an inverse table. Four
+//! bytes fed from register `S` act like four zero bytes fed from `S` XOR the
+//! bytes, so the patch is `S` XOR the register that four zero bytes turn
+//! into the target.
+
+use crate::core::{with_tables, CrcEngine};
+use crate::Algorithm32;
+
+impl Algorithm32 {
+    /// Compute the four bytes that, appended to `data`, make its CRC equal
+    /// `target`.
+    ///
+    /// ```
+    /// use fastcrc::CRC32;
+    ///
+    /// let mut firmware = b"firmware image".to_vec();
+    /// let patch = CRC32.forge_append(&firmware, 0xC0FF_EE00);
+    /// firmware.extend_from_slice(&patch);
+    /// assert_eq!(CRC32.checksum(&firmware), 0xC0FF_EE00);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the CRC is narrower than 32 bits or its polynomial lacks
+    /// the `+ 1` term.
+    pub fn forge_append(&self, data: &[u8], target: u32) -> [u8; 4] {
+        Forger::new(*self).forge(data, &[], target)
+    }
+
+    /// Compute the four bytes that, written over `data[offset..offset + 4]`,
+    /// make the CRC of `data` equal `target`.
+    ///
+    /// The current contents of the patched range are ignored. Runs in time
+    /// linear in `data.len()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset + 4` exceeds `data.len()`, the CRC is narrower than
+    /// 32 bits or its polynomial lacks the `+ 1` term.
+    pub fn forge_at(&self, data: &[u8], offset: usize, target: u32) -> [u8; 4] {
+        assert!(
+            offset.checked_add(4).is_some_and(|end| end <= data.len()),
+            "patch range out of bounds"
+        );
+        Forger::new(*self).forge(&data[..offset], &data[offset + 4..], target)
+    }
+}
+
+/// The forward byte table of a 32-bit CRC together with its inverse.
+struct Forger {
+    params: Algorithm32,
+    table: [u32; 256],
+    inverse: [u8; 256],
+}
+
+impl Forger {
+    fn new(params: Algorithm32) -> Self {
+        assert!(params.width == 32, "CRC forging needs a 32-bit CRC");
+        assert!(
+            params.polynomial & 1 != 0,
+            "CRC forging needs a polynomial with a + 1 term"
+        );
+        let table = params.table();
+        let mut inverse = [0; 256];
+        for (idx, &entry) in table.iter().enumerate() {
+            inverse[usize::from(Self::edge_byte(params, entry))] = idx as u8;
+        }
+        Self {
+            params,
+            table,
+            inverse,
+        }
+    }
+
+    /// The byte of a table entry left where the step shifted its input out:
+    /// the top byte for reflected CRCs and the bottom byte otherwise.
+    fn edge_byte(params: Algorithm32, entry: u32) -> u8 {
+        if params.reflect_in {
+            (entry >> 24) as u8
+        } else {
+            entry as u8
+        }
+    }
+
+    /// Find the patch placed between `prefix` and `suffix`.
+    fn forge(&self, prefix: &[u8], suffix: &[u8], target: u32) -> [u8; 4] {
+        let (before, mut after) = with_tables(self.params, |tables| {
+            let mut engine = CrcEngine::new(tables);
+            engine.update(prefix);
+            (
+                engine.state(),
+                CrcEngine::with_checksum(tables, target, 0).state(),
+            )
+        });
+        for &byte in suffix.iter().rev() {
+            after = self.unstep(after, byte);
+        }
+        for _ in 0..4 {
+            after = self.unstep(after, 0);
+        }
+
+        let patch = before ^ after;
+        if self.params.reflect_in {
+            patch.to_le_bytes()
+        } else {
+            patch.to_be_bytes()
+        }
+    }
+
+    /// Undo the table step that fed `byte` into the register.
+    fn unstep(&self, state: u32, byte: u8) -> u32 {
+        let idx = self.inverse[usize::from(Self::edge_byte(self.params, state))];
+        let rest = state ^ self.table[usize::from(idx)];
+        if self.params.reflect_in {
+            rest << 8 | u32::from(idx ^ byte)
+        } else {
+            rest >> 8 | u32::from(idx ^ byte) << 24
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Algorithm32, CRC32, CRC32C, CRC32_BZIP2, CRC32_CKSUM};
+
+    const ALGORITHMS: &[Algorithm32] = &[
+        CRC32,
+        CRC32C,
+        CRC32_BZIP2,
+        CRC32_CKSUM,
+        Algorithm32::new("mixed", 0x04C11DB7, 0x1234_5678, 0x9ABC_DEF0, true, false),
+        Algorithm32::new("mixed", 0x1EDC6F41, 0x0BAD_F00D, 0, false, true),
+    ];
+
+    fn sample_data() -> Vec<u8> {
+        (0..300u32)
+            .map(|i| (i.wrapping_mul(29) >> 2) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn appended_bytes_hit_target() {
+        let data = sample_data();
+        for params in ALGORITHMS {
+            for (len, target) in [(0, 0), (1, 0xFFFF_FFFF), (300, 0xDEAD_BEEF)] {
+                let mut forged = data[..len].to_vec();
+                forged.extend_from_slice(&params.forge_append(&data[..len], target));
+                assert_eq!(params.checksum(&forged), target, "{}", params.name);
+            }
+        }
+    }
+
+    #[test]
+    fn patched_bytes_hit_target() {
+        let data = sample_data();
+        for params in ALGORITHMS {
+            for offset in [0, 1, 150, data.len() - 4] {
+                let mut forged = data.clone();
+                let patch = params.forge_at(&data, offset, 0x1234_5678);
+                forged[offset..offset + 4].copy_from_slice(&patch);
+                assert_eq!(params.checksum(&forged), 0x1234_5678, "{}", params.name);
+            }
+        }
+    }
+
+    #[test]
+    fn patching_is_idempotent_for_current_checksum() {
+        let data = sample_data();
+        let patch = CRC32.forge_at(&data, 40, CRC32.checksum(&data));
+        assert_eq!(patch, data[40..44]);
+    }
+
+    #[test]
+    #[should_panic(expected = "patch range out of bounds")]
+    fn patch_beyond_end_is_rejected() {
+        CRC32.forge_at(b"abc", 0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "CRC forging needs a 32-bit CRC")]
+    fn narrow_crc_is_rejected() {
+        Algorithm32::with_width("crc24", 24, 0x864CFB, 0xB704CE, 0, false, false)
+            .forge_append(b"", 0);
+    }
+}
@@ -0,0 +1,378 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::fmt;
+use core::marker::PhantomData;
+
+#[cfg(feature = "digest")]
+use digest::core_api::OutputSizeUser;
+#[cfg(feature = "digest")]
+use digest::typenum::{U1, U2, U4, U8};
+#[cfg(feature = "digest")]
+use digest::{FixedOutput, FixedOutputReset, HashMarker, Output, Reset, Update};
+
+#[cfg(feature = "serde")]
+use crate::core::SerdeState;
+use crate::core::{
+    fmt_digest, Algorithm16, Algorithm32, Algorithm64, Algorithm8, Checksum, CrcEngine, CrcTables,
+};
+
+/// Binds an [`Algorithm8`] to a type so it can parameterize [`GenericCrc8`].
+pub trait Crc8Algorithm {
+    /// The CRC8 variant computed by [`GenericCrc8<Self>`].
+    const ALGORITHM: Algorithm8;
+}
+
+/// Binds an [`Algorithm16`] to a type so it can parameterize [`GenericCrc16`].
+pub trait Crc16Algorithm {
+    /// The CRC16 variant computed by [`GenericCrc16<Self>`].
+    const ALGORITHM: Algorithm16;
+}
+
+/// Binds an [`Algorithm32`] to a type so it can parameterize [`GenericCrc32`].
+pub trait Crc32Algorithm {
+    /// The CRC32 variant computed by [`GenericCrc32<Self>`].
+    const ALGORITHM: Algorithm32;
+}
+
+/// Binds an [`Algorithm64`] to a type so it can parameterize [`GenericCrc64`].
+pub trait Crc64Algorithm {
+    /// The CRC64 variant computed by [`GenericCrc64<Self>`].
+    const ALGORITHM: Algorithm64;
+}
+
+macro_rules! generic_digest {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $algorithm:ident, $word:ty, $output:ty, $finalize:ident
+    ) => {
+        $(#[$meta])*
+        #[cfg_attr(
+            feature = "digest",
+            doc = "",
+            doc = "Implements the RustCrypto [`digest::Digest`] trait through its blanket impl."
+        )]
+        pub struct $name<A: $algorithm> {
+            inner: CrcEngine<'static, $word>,
+            _algorithm: PhantomData<fn() -> A>,
+        }
+
+        impl<A: $algorithm> $name<A> {
+            /// Tables for `A`, evaluated once per algorithm at compile time.
+            const TABLES: &'static CrcTables<$word> = &A::ALGORITHM.tables();
+
+            /// Create a new digest instance for `A`.
+            pub fn new() -> Self {
+                Self {
+                    inner: CrcEngine::new(Self::TABLES),
+                    _algorithm: PhantomData,
+                }
+            }
+
+            /// Resume a digest from a register saved with
+            /// [`state`](Self::state) after `bytes_processed` bytes.
+            pub fn from_state(state: $word, bytes_processed: u64) -> Self {
+                Self {
+                    inner: CrcEngine::with_state(Self::TABLES, state, bytes_processed),
+                    _algorithm: PhantomData,
+                }
+            }
+
+            /// Resume a digest from a CRC previously returned by
+            #[doc = concat!("[`", stringify!($finalize), "`](Self::", stringify!($finalize), ") after `bytes_processed`")]
+            /// bytes, so that further updates extend the original input.
+            pub fn from_checksum(crc: $word, bytes_processed: u64) -> Self {
+                Self {
+                    inner: CrcEngine::with_checksum(Self::TABLES, crc, bytes_processed),
+                    _algorithm: PhantomData,
+                }
+            }
+
+            /// Return the CRC register before the final reflection and XOR,
+            /// which together with [`bytes_processed`](Self::bytes_processed)
+            /// can be persisted and resumed with [`from_state`](Self::from_state).
+            pub fn state(&self) -> $word {
+                self.inner.state()
+            }
+
+            /// Return the number of bytes fed since creation or the last reset.
+            pub fn bytes_processed(&self) -> u64 {
+                self.inner.len()
+            }
+
+            /// Update the digest state with additional bytes.
+            pub fn update(&mut self, data: &[u8]) {
+                self.inner.update(data);
+            }
+
+            /// Reset the digest to its initial value.
+            pub fn reset(&mut self) {
+                self.inner.reset();
+            }
+
+            #[doc = concat!("Retrieve the checksum as `", stringify!($word), "`.")]
+            pub fn $finalize(self) -> $word {
+                self.inner.finalize()
+            }
+
+            /// Compute the CRC of `A || B` from `crc_a = crc(A)`, `crc_b = crc(B)`
+            /// and the length of `B` in bytes.
+            pub fn combine(crc_a: $word, crc_b: $word, len_b: u64) -> $word {
+                A::ALGORITHM.combine(crc_a, crc_b, len_b)
+            }
+        }
+
+        impl<A: $algorithm> Clone for $name<A> {
+            fn clone(&self) -> Self {
+                Self {
+                    inner: self.inner,
+                    _algorithm: PhantomData,
+                }
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<A: $algorithm> serde::Serialize for $name<A> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let state = SerdeState {
+                    state: self.state(),
+                    bytes_processed: self.bytes_processed(),
+                };
+                serde::Serialize::serialize(&state, serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de, A: $algorithm> serde::Deserialize<'de> for $name<A> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let state: SerdeState<$word> = serde::Deserialize::deserialize(deserializer)?;
+                Ok(Self::from_state(state.state, state.bytes_processed))
+            }
+        }
+
+        impl<A: $algorithm> Default for $name<A> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<A: $algorithm> fmt::Debug for $name<A> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt_digest(f, stringify!($name), &A::ALGORITHM, self.inner.finalize())
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl<A: $algorithm> OutputSizeUser for $name<A> {
+            type OutputSize = $output;
+        }
+
+        #[cfg(feature = "digest")]
+        impl<A: $algorithm> Update for $name<A> {
+            fn update(&mut self, data: &[u8]) {
+                self.inner.update(data);
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl<A: $algorithm> Reset for $name<A> {
+            fn reset(&mut self) {
+                self.inner.reset();
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl<A: $algorithm> FixedOutput for $name<A> {
+            fn finalize_into(self, out: &mut Output<Self>) {
+                self.inner.finalize_into(out);
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl<A: $algorithm> FixedOutputReset for $name<A> {
+            fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+                self.inner.finalize_into_reset(out);
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl<A: $algorithm> HashMarker for $name<A> {}
+
+        impl<A: $algorithm> Checksum for $name<A> {
+            type Value = $word;
+
+            fn update(&mut self, data: &[u8]) {
+                self.inner.update(data);
+            }
+
+            fn reset(&mut self) {
+                self.inner.reset();
+            }
+
+            fn checksum(&self) -> $word {
+                self.inner.finalize()
+            }
+        }
+    };
+}
+
+generic_digest!(
+    /// CRC8 digest for any [`Crc8Algorithm`].
+    GenericCrc8,
+    Crc8Algorithm,
+    u8,
+    U1,
+    finalize_u8
+);
+
+generic_digest!(
+    /// CRC16 digest for any [`Crc16Algorithm`].
+    GenericCrc16,
+    Crc16Algorithm,
+    u16,
+    U2,
+    finalize_u16
+);
+
+generic_digest!(
+    /// CRC32 digest for any [`Crc32Algorithm`].
+    ///
+    /// ```
+    /// use fastcrc::{Algorithm32, Crc32Algorithm, GenericCrc32};
+    ///
+    /// const CRC32_BZIP2: Algorithm32 =
+    ///     Algorithm32::new("crc32-bzip2", 0x04C11DB7, 0xFFFF_FFFF, 0xFFFF_FFFF, false, false);
+    ///
+    /// struct Bzip2;
+    ///
+    /// impl Crc32Algorithm for Bzip2 {
+    ///     const ALGORITHM: Algorithm32 = CRC32_BZIP2;
+    /// }
+    ///
+    /// let mut digest = GenericCrc32::<Bzip2>::new();
+    /// digest.update(b"123456789");
+    /// assert_eq!(digest.finalize_u32(), 0xFC89_1918);
+    /// ```
+    GenericCrc32,
+    Crc32Algorithm,
+    u32,
+    U4,
+    finalize_u32
+);
+
+generic_digest!(
+    /// CRC64 digest for any [`Crc64Algorithm`].
+    GenericCrc64,
+    Crc64Algorithm,
+    u64,
+    U8,
+    finalize_u64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crc32, crc32c, crc64_xz, CRC32, CRC32C, CRC64_XZ};
+
+    struct Ieee;
+
+    impl Crc32Algorithm for Ieee {
+        const ALGORITHM: Algorithm32 = CRC32;
+    }
+
+    struct Castagnoli;
+
+    impl Crc32Algorithm for Castagnoli {
+        const ALGORITHM: Algorithm32 = CRC32C;
+    }
+
+    /// CRC-32/MPEG-2 from the RevEng CRC Catalogue.
+    struct Mpeg2;
+
+    impl Crc32Algorithm for Mpeg2 {
+        const ALGORITHM: Algorithm32 =
+            Algorithm32::new("crc32-mpeg2", 0x04C11DB7, 0xFFFF_FFFF, 0, false, false);
+    }
+
+    struct Xz;
+
+    impl Crc64Algorithm for Xz {
+        const ALGORITHM: Algorithm64 = CRC64_XZ;
+    }
+
+    struct Umts;
+
+    impl Crc16Algorithm for Umts {
+        const ALGORITHM: Algorithm16 =
+            Algorithm16::with_width("crc12-umts", 12, 0x80F, 0, 0, false, true);
+    }
+
+    #[test]
+    fn generic_matches_builtin_digests() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let mut ieee = GenericCrc32::<Ieee>::new();
+        ieee.update(data);
+        assert_eq!(ieee.finalize_u32(), crc32(data));
+
+        let mut castagnoli = GenericCrc32::<Castagnoli>::new();
+        castagnoli.update(data);
+        assert_eq!(castagnoli.finalize_u32(), crc32c(data));
+
+        let mut xz = GenericCrc64::<Xz>::new();
+        xz.update(data);
+        assert_eq!(xz.finalize_u64(), crc64_xz(data));
+    }
+
+    #[test]
+    fn generic_custom_algorithm_check_value() {
+        let mut digest = GenericCrc32::<Mpeg2>::new();
+        digest.update(b"123456789");
+        assert_eq!(digest.finalize_u32(), 0x0376_E6E7);
+
+        #[cfg(feature = "digest")]
+        {
+            let output = <GenericCrc32<Mpeg2> as digest::Digest>::digest(b"123456789");
+            assert_eq!(output[..], 0x0376_E6E7u32.to_be_bytes());
+        }
+    }
+
+    #[test]
+    fn generic_debug_shows_checksum() {
+        let mut digest = GenericCrc16::<Umts>::new();
+        digest.update(b"123456789");
+        assert_eq!(
+            format!("{digest:?}"),
+            r#"GenericCrc16 { algorithm: "crc12-umts", checksum: 0xdaf }"#
+        );
+    }
+
+    #[test]
+    fn generic_resumes_from_saved_state() {
+        let data = b"123456789";
+        let mut digest = GenericCrc32::<Mpeg2>::new();
+        digest.update(&data[..4]);
+        let mut resumed = GenericCrc32::<Mpeg2>::from_state(digest.state(), 4);
+        resumed.update(&data[4..]);
+        assert_eq!(resumed.finalize_u32(), 0x0376_E6E7);
+
+        #[cfg(feature = "serde")]
+        {
+            let saved = serde_json::to_vec(&digest).unwrap();
+            let mut resumed: GenericCrc32<Mpeg2> = serde_json::from_slice(&saved).unwrap();
+            assert_eq!(resumed.bytes_processed(), 4);
+            resumed.update(&data[4..]);
+            assert_eq!(resumed.finalize_u32(), 0x0376_E6E7);
+        }
+    }
+}
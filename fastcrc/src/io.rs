@@ -0,0 +1,236 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [`std::io`] adapters that checksum bytes while forwarding them.
+
+use std::io;
+
+use crate::core::Checksum;
+
+/// Wraps a [`Write`](io::Write) and checksums every byte the inner writer
+/// accepted.
+///
+/// ```
+/// use std::io::Write;
+///
+/// use fastcrc::{Crc32, CrcWriter};
+///
+/// let mut writer = CrcWriter::<_, Crc32>::new(Vec::new());
+/// writer.write_all(b"123456789").unwrap();
+/// assert_eq!(writer.finalize_u32(), 0xCBF4_3926);
+/// assert_eq!(writer.into_inner(), b"123456789");
+/// ```
+#[derive(Clone, Debug)]
+pub struct CrcWriter<W, D> {
+    inner: W,
+    digest: D,
+}
+
+impl<W, D: Default> CrcWriter<W, D> {
+    /// Wrap `inner` with a fresh digest.
+    pub fn new(inner: W) -> Self {
+        Self::with_digest(inner, D::default())
+    }
+}
+
+impl<W, D> CrcWriter<W, D> {
+    /// Wrap `inner`, continuing from the state of `digest`.
+    pub fn with_digest(inner: W, digest: D) -> Self {
+        Self { inner, digest }
+    }
+
+    /// Borrow the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Mutably borrow the inner writer.
+    ///
+    /// Bytes written directly to it are not checksummed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Borrow the digest.
+    pub fn digest(&self) -> &D {
+        &self.digest
+    }
+
+    /// Unwrap the inner writer, discarding the digest.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Unwrap the inner writer and the digest.
+    pub fn into_parts(self) -> (W, D) {
+        (self.inner, self.digest)
+    }
+}
+
+impl<W, D: Checksum> CrcWriter<W, D> {
+    /// Return the CRC of the bytes written so far.
+    pub fn checksum(&self) -> D::Value {
+        self.digest.checksum()
+    }
+}
+
+impl<W: io::Write, D: Checksum> io::Write for CrcWriter<W, D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.digest.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Wraps a [`Read`](io::Read) and checksums every byte read from the inner
+/// reader.
+///
+/// ```
+/// use std::io::Read;
+///
+/// use fastcrc::{Crc32c, CrcReader};
+///
+/// let mut reader = CrcReader::<_, Crc32c>::new(&b"123456789"[..]);
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf).unwrap();
+/// assert_eq!(reader.finalize_u32(), 0xE306_9283);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CrcReader<R, D> {
+    inner: R,
+    digest: D,
+}
+
+impl<R, D: Default> CrcReader<R, D> {
+    /// Wrap `inner` with a fresh digest.
+    pub fn new(inner: R) -> Self {
+        Self::with_digest(inner, D::default())
+    }
+}
+
+impl<R, D> CrcReader<R, D> {
+    /// Wrap `inner`, continuing from the state of `digest`.
+    pub fn with_digest(inner: R, digest: D) -> Self {
+        Self { inner, digest }
+    }
+
+    /// Borrow the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Mutably borrow the inner reader.
+    ///
+    /// Bytes read directly from it are not checksummed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Borrow the digest.
+    pub fn digest(&self) -> &D {
+        &self.digest
+    }
+
+    /// Unwrap the inner reader, discarding the digest.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Unwrap the inner reader and the digest.
+    pub fn into_parts(self) -> (R, D) {
+        (self.inner, self.digest)
+    }
+}
+
+impl<R, D: Checksum> CrcReader<R, D> {
+    /// Return the CRC of the bytes read so far.
+    pub fn checksum(&self) -> D::Value {
+        self.digest.checksum()
+    }
+}
+
+impl<R: io::Read, D: Checksum> io::Read for CrcReader<R, D> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.digest.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+impl_adapter_finalize!(CrcWriter, CrcReader);
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+
+    use super::*;
+    use crate::{crc32, crc64_xz, Crc32, Crc64Xz};
+
+    /// Writer accepting at most `limit` bytes per call.
+    struct Trickle {
+        written: Vec<u8>,
+        limit: usize,
+    }
+
+    impl io::Write for Trickle {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.limit);
+            self.written.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writer_checksums_only_accepted_bytes() {
+        let trickle = Trickle {
+            written: Vec::new(),
+            limit: 3,
+        };
+        let mut writer = CrcWriter::<_, Crc32>::new(trickle);
+        assert_eq!(writer.write(b"123456789").unwrap(), 3);
+        assert_eq!(writer.finalize_u32(), crc32(b"123"));
+        writer.write_all(b"456789").unwrap();
+        assert_eq!(writer.finalize_u32(), 0xCBF4_3926);
+        assert_eq!(writer.into_inner().written, b"123456789");
+    }
+
+    #[test]
+    fn reader_checksums_bytes_read() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        let mut reader = CrcReader::<_, Crc64Xz>::new(&data[..]);
+        let mut buf = [0u8; 64];
+        assert_eq!(reader.read(&mut buf).unwrap(), 64);
+        assert_eq!(reader.finalize_u64(), crc64_xz(&data[..64]));
+        io::copy(&mut reader, &mut io::sink()).unwrap();
+        assert_eq!(reader.checksum(), crc64_xz(&data));
+    }
+
+    #[test]
+    fn writer_resumes_from_digest() {
+        let mut digest = Crc32::new();
+        digest.update(b"1234");
+        let mut writer = CrcWriter::with_digest(io::sink(), digest);
+        writer.write_all(b"56789").unwrap();
+        let (_, digest) = writer.into_parts();
+        assert_eq!(digest.finalize_u32(), 0xCBF4_3926);
+    }
+}
@@ -18,12 +18,147 @@
 //! algorithm descriptions, while algorithm-specific modules such as [`crc32`]
 //! and [`crc32c`] expose ergonomic digest types. The crate root re-exports the
 //! most common types so users can depend on `fastcrc` alone.
+//!
+//! Algorithms without a dedicated digest type can be described with an
+//! [`Algorithm8`], [`Algorithm16`], [`Algorithm32`] or [`Algorithm64`]
+//! constant and used through the matching [`GenericCrc8`], [`GenericCrc16`],
+//! [`GenericCrc32`] or [`GenericCrc64`] digest.
+//!
+//! Checksums of literal byte strings can be computed at compile time:
+//!
+//! ```
+//! use fastcrc::CRC32;
+//!
+//! const MAGIC: u32 = CRC32.checksum_const(b"FCRC");
+//!
+//! match fastcrc::crc32(b"FCRC") {
+//!     MAGIC => {}
+//!     _ => unreachable!(),
+//! }
+//! ```
+//!
+//! # Features
+//!
+//! The crate is `no_std` and allocation-free unless the `std` feature is
+//! enabled. Each digest type used by a program links one `static` set of
+//! slicing-by-16 tables: 4 KiB for CRC-8, 8 KiB for CRC-16, 16 KiB for
+//! CRC-32 and 32 KiB for CRC-64 digests. Without `std`,
+//! [`Algorithm::checksum`] instead builds a single 256-entry table, at most
+//! 2 KiB, on the stack on every call; with `std` it reuses the static tables
+//! of a matching built-in algorithm, which links every built-in table set of
+//! that register width. Checksums evaluated with
+//! [`Algorithm::checksum_const`] at compile time cost nothing at runtime.
+//!
+#![cfg_attr(
+    feature = "std",
+    doc = "- `std` (default): [`CrcReader`] and [`CrcWriter`] adapters, the
+  multithreaded `checksum_parallel` and `checksum_file_parallel` helpers,
+  the [`Registry`] of algorithms by catalogue name, the [`Solver`] for
+  recovering unknown CRC parameters from samples, the [`RollingCrc32`]
+  sliding-window CRC and the content-defined [`Chunker`]."
+)]
+#![cfg_attr(
+    not(feature = "std"),
+    doc = "- `std` (default): `CrcReader` and `CrcWriter` adapters, the
+  multithreaded `checksum_parallel` and `checksum_file_parallel` helpers,
+  the `Registry` of algorithms by catalogue name, the `Solver` for
+  recovering unknown CRC parameters from samples, the `RollingCrc32`
+  sliding-window CRC and the content-defined `Chunker`."
+)]
+#![cfg_attr(
+    feature = "digest",
+    doc = "- `digest` (default): the digest types implement the RustCrypto `digest`
+  traits, including [`digest::Digest`] through its blanket impl."
+)]
+#![cfg_attr(
+    not(feature = "digest"),
+    doc = "- `digest` (default): the digest types implement the RustCrypto `digest`
+  traits, including `digest::Digest` through its blanket impl."
+)]
+//! - `serde`: digests serialize as their resumable `state()` and
+//!   `bytes_processed()`.
+//! - `simd`: use hardware CRC instructions when the running CPU supports them
+//!   (SSE4.2 `crc32` for CRC32C and PCLMULQDQ folding for every reflected
+//!   CRC32 variant on x86_64), falling back to the portable table engine
+//!   otherwise.
+//! - `tokio`: `AsyncCrcReader` and `AsyncCrcWriter` implement tokio's
+//!   `AsyncRead` and `AsyncWrite`.
+//! - `futures-io`: `AsyncCrcReader` and `AsyncCrcWriter` implement
+//!   futures-io's `AsyncRead` and `AsyncWrite`.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(unsafe_code)]
 
+#[macro_use]
+mod macros;
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+mod async_io;
+#[cfg(feature = "std")]
+mod chunker;
+mod combine;
 mod core;
+mod crc16;
 mod crc32;
 mod crc32c;
+mod crc64;
+mod crc8;
+mod forge;
+mod generic;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "std")]
+mod parallel;
+#[cfg(feature = "std")]
+mod registry;
+#[cfg(feature = "std")]
+mod rolling;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+#[allow(unsafe_code)]
+mod simd;
+#[cfg(feature = "std")]
+mod solver;
+
+#[cfg(feature = "std")]
+pub use crate::chunker::{Chunk, Chunker, Chunks, ReadChunks};
+pub use crate::core::{Algorithm, Algorithm16, Algorithm32, Algorithm64, Algorithm8, Checksum};
+pub use crate::crc16::{
+    crc16_arc, crc16_dnp, crc16_genibus, crc16_ibm_3740, crc16_ibm_sdlc, crc16_kermit,
+    crc16_modbus, crc16_t10_dif, crc16_usb, crc16_xmodem, Crc16Arc, Crc16Dnp, Crc16Genibus,
+    Crc16Ibm3740, Crc16IbmSdlc, Crc16Kermit, Crc16Modbus, Crc16T10Dif, Crc16Usb, Crc16Xmodem,
+    CRC16_ARC, CRC16_DNP, CRC16_GENIBUS, CRC16_IBM_3740, CRC16_IBM_SDLC, CRC16_KERMIT,
+    CRC16_MODBUS, CRC16_T10_DIF, CRC16_USB, CRC16_XMODEM,
+};
+pub use crate::crc32::{
+    crc32, crc32_aixm, crc32_autosar, crc32_base91_d, crc32_bzip2, crc32_cd_rom_edc, crc32_cksum,
+    crc32_jamcrc, crc32_mef, crc32_mpeg_2, crc32_xfer, Crc32, Crc32Aixm, Crc32Autosar,
+    Crc32Base91D, Crc32BuildHasher, Crc32Bzip2, Crc32CdRomEdc, Crc32Cksum, Crc32Jamcrc, Crc32Mef,
+    Crc32Mpeg2, Crc32Xfer, CRC32, CRC32_AIXM, CRC32_AUTOSAR, CRC32_BASE91_D, CRC32_BZIP2,
+    CRC32_CD_ROM_EDC, CRC32_CKSUM, CRC32_JAMCRC, CRC32_MEF, CRC32_MPEG_2, CRC32_XFER,
+};
+pub use crate::crc32c::{crc32c, Crc32c, Crc32cBuildHasher, CRC32C};
+pub use crate::crc64::{
+    crc64_ecma_182, crc64_go_iso, crc64_nvme, crc64_redis, crc64_we, crc64_xz, Crc64Ecma182,
+    Crc64GoIso, Crc64Nvme, Crc64Redis, Crc64We, Crc64Xz, CRC64_ECMA_182, CRC64_GO_ISO, CRC64_NVME,
+    CRC64_REDIS, CRC64_WE, CRC64_XZ,
+};
+pub use crate::crc8::{
+    crc8_autosar, crc8_bluetooth, crc8_dvb_s2, crc8_maxim_dow, crc8_sae_j1850, crc8_smbus,
+    Crc8Autosar, Crc8Bluetooth, Crc8DvbS2, Crc8MaximDow, Crc8SaeJ1850, Crc8Smbus, CRC8_AUTOSAR,
+    CRC8_BLUETOOTH, CRC8_DVB_S2, CRC8_MAXIM_DOW, CRC8_SAE_J1850, CRC8_SMBUS,
+};
+pub use crate::generic::{
+    Crc16Algorithm, Crc32Algorithm, Crc64Algorithm, Crc8Algorithm, GenericCrc16, GenericCrc32,
+    GenericCrc64, GenericCrc8,
+};
+#[cfg(feature = "std")]
+pub use crate::io::{CrcReader, CrcWriter};
+#[cfg(feature = "std")]
+pub use crate::registry::{lookup, AnyAlgorithm, DynChecksum, Entry, Registry};
+#[cfg(feature = "std")]
+pub use crate::rolling::RollingCrc32;
+#[cfg(feature = "std")]
+pub use crate::solver::{Solution, Solver};
 
-pub use crate::crc32::{crc32, Crc32};
-pub use crate::crc32c::{crc32c, Crc32c};
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub use crate::async_io::{AsyncCrcReader, AsyncCrcWriter};
@@ -0,0 +1,245 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Define a digest type hosting a fixed algorithm together with its one-shot
+/// helper.
+macro_rules! define_digest {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $oneshot:ident, $algorithm:expr, $word:ty, $output:ty, $finalize:ident, $label:literal
+    ) => {
+        $(#[$meta])*
+        #[cfg_attr(
+            feature = "digest",
+            doc = "",
+            doc = "Implements the RustCrypto [`digest::Digest`] trait through its blanket impl."
+        )]
+        #[derive(Clone)]
+        pub struct $name {
+            inner: $crate::core::CrcEngine<'static, $word>,
+        }
+
+        impl $name {
+            #[doc = concat!("Create a new ", $label, " digest instance.")]
+            pub fn new() -> Self {
+                Self {
+                    inner: $crate::core::CrcEngine::new(Self::tables()),
+                }
+            }
+
+            /// Resume a digest from a register saved with
+            /// [`state`](Self::state) after `bytes_processed` bytes.
+            pub fn from_state(state: $word, bytes_processed: u64) -> Self {
+                Self {
+                    inner: $crate::core::CrcEngine::with_state(
+                        Self::tables(),
+                        state,
+                        bytes_processed,
+                    ),
+                }
+            }
+
+            /// Resume a digest from a CRC previously returned by
+            #[doc = concat!("[`", stringify!($finalize), "`](Self::", stringify!($finalize), ") after `bytes_processed`")]
+            /// bytes, so that further updates extend the original input.
+            pub fn from_checksum(crc: $word, bytes_processed: u64) -> Self {
+                Self {
+                    inner: $crate::core::CrcEngine::with_checksum(
+                        Self::tables(),
+                        crc,
+                        bytes_processed,
+                    ),
+                }
+            }
+
+            /// Return the CRC register before the final reflection and XOR,
+            /// which together with [`bytes_processed`](Self::bytes_processed)
+            /// can be persisted and resumed with [`from_state`](Self::from_state).
+            pub fn state(&self) -> $word {
+                self.inner.state()
+            }
+
+            /// Return the number of bytes fed since creation or the last reset.
+            pub fn bytes_processed(&self) -> u64 {
+                self.inner.len()
+            }
+
+            /// Static tables shared by every digest of this type.
+            pub(crate) fn tables() -> &'static $crate::core::CrcTables<$word> {
+                static TABLES: $crate::core::CrcTables<$word> = $algorithm.tables();
+                &TABLES
+            }
+
+            /// Update the digest state with additional bytes.
+            pub fn update(&mut self, data: &[u8]) {
+                self.inner.update(data);
+            }
+
+            /// Reset the digest to its initial value.
+            pub fn reset(&mut self) {
+                self.inner.reset();
+            }
+
+            #[doc = concat!("Retrieve the checksum as `", stringify!($word), "`.")]
+            pub fn $finalize(self) -> $word {
+                self.inner.finalize()
+            }
+
+            #[doc = concat!("Compute the ", $label, " of `A || B` from `crc_a = crc(A)`, `crc_b = crc(B)`")]
+            /// and the length of `B` in bytes.
+            pub fn combine(crc_a: $word, crc_b: $word, len_b: u64) -> $word {
+                $algorithm.combine(crc_a, crc_b, len_b)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $name {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let state = $crate::core::SerdeState {
+                    state: self.state(),
+                    bytes_processed: self.bytes_processed(),
+                };
+                ::serde::Serialize::serialize(&state, serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let state: $crate::core::SerdeState<$word> =
+                    ::serde::Deserialize::deserialize(deserializer)?;
+                Ok(Self::from_state(state.state, state.bytes_processed))
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                $crate::core::fmt_digest(f, stringify!($name), &$algorithm, self.inner.finalize())
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl ::digest::core_api::OutputSizeUser for $name {
+            type OutputSize = $output;
+        }
+
+        #[cfg(feature = "digest")]
+        impl ::digest::Update for $name {
+            fn update(&mut self, data: &[u8]) {
+                self.inner.update(data);
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl ::digest::Reset for $name {
+            fn reset(&mut self) {
+                self.inner.reset();
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl ::digest::FixedOutput for $name {
+            fn finalize_into(self, out: &mut ::digest::Output<Self>) {
+                ::digest::FixedOutput::finalize_into(self.inner, out);
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl ::digest::FixedOutputReset for $name {
+            fn finalize_into_reset(&mut self, out: &mut ::digest::Output<Self>) {
+                ::digest::FixedOutputReset::finalize_into_reset(&mut self.inner, out);
+            }
+        }
+
+        #[cfg(feature = "digest")]
+        impl ::digest::HashMarker for $name {}
+
+        impl $crate::Checksum for $name {
+            type Value = $word;
+
+            fn update(&mut self, data: &[u8]) {
+                self.inner.update(data);
+            }
+
+            fn reset(&mut self) {
+                self.inner.reset();
+            }
+
+            fn checksum(&self) -> $word {
+                self.inner.finalize()
+            }
+        }
+
+        #[doc = concat!("One-shot helper for calculating ", $label, " over a byte slice.")]
+        pub fn $oneshot(data: &[u8]) -> $word {
+            let mut digest = $name::new();
+            digest.update(data);
+            digest.$finalize()
+        }
+    };
+}
+
+/// Implement [`Hasher`](core::hash::Hasher) for a 32-bit digest defined with
+/// `define_digest!` and name the matching `BuildHasherDefault`.
+macro_rules! impl_hasher {
+    ($name:ident, $build_hasher:ident, $label:literal) => {
+        #[doc = concat!("Hashes the written bytes with ", $label, ".")]
+        ///
+        /// [`finish`](::core::hash::Hasher::finish) widens the CRC to 64 bits
+        /// by repeating it in both halves: `finish() as u32` equals
+        #[doc = concat!("[`finalize_u32`](", stringify!($name), "::finalize_u32), while hash")]
+        /// tables that take their bits from the top of the hash still see the
+        /// whole CRC.
+        impl ::core::hash::Hasher for $name {
+            fn finish(&self) -> u64 {
+                u64::from(self.inner.finalize()) * 0x1_0000_0001
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                self.inner.update(bytes);
+            }
+        }
+
+        #[doc = concat!("Builds [`", stringify!($name), "`] hashers, e.g. for `HashMap::with_hasher`.")]
+        pub type $build_hasher = ::core::hash::BuildHasherDefault<$name>;
+    };
+}
+
+/// Add `finalize_u8` through `finalize_u64` accessors to checksumming I/O
+/// adapters with `inner` and `digest` fields, one per digest width.
+#[cfg(feature = "std")]
+macro_rules! impl_adapter_finalize {
+    ($($adapter:ident),* $(,)?) => {
+        $(
+            impl_adapter_finalize!(@word $adapter, u8, finalize_u8);
+            impl_adapter_finalize!(@word $adapter, u16, finalize_u16);
+            impl_adapter_finalize!(@word $adapter, u32, finalize_u32);
+            impl_adapter_finalize!(@word $adapter, u64, finalize_u64);
+        )*
+    };
+    (@word $adapter:ident, $word:ty, $finalize:ident) => {
+        impl<T, D: $crate::Checksum<Value = $word>> $adapter<T, D> {
+            #[doc = concat!("Return the CRC of the bytes passed through so far as `", stringify!($word), "`.")]
+            pub fn $finalize(&self) -> $word {
+                self.digest.checksum()
+            }
+        }
+    };
+}
@@ -0,0 +1,267 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Multithreaded checksumming built on CRC combination.
+//!
+//! Input is split into contiguous chunks that are checksummed on scoped
+//! threads; the partial CRCs are then merged in order with
+//! [`Algorithm::combine`], producing the same value as a sequential run.
+//! Every worker of a run shares one set of lookup tables.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::thread;
+
+use crate::combine::combine;
+use crate::core::{checksum_with, with_tables, Algorithm, CrcEngine, CrcTables, Word};
+
+/// Smallest chunk handed to a worker thread; smaller inputs are not worth the
+/// cost of spawning.
+const MIN_CHUNK: usize = 64 * 1024;
+
+/// Size of the read buffer used by each file worker.
+const READ_BUFFER: usize = 256 * 1024;
+
+macro_rules! impl_parallel {
+    ($($word:ty),*) => {$(
+        impl Algorithm<$word> {
+            /// Compute the CRC of `data` using up to `threads` threads.
+            ///
+            /// The result equals [`checksum`](Self::checksum). No more threads
+            /// are spawned than [`std::thread::available_parallelism`] reports
+            /// or than there are 64 KiB parts of `data`; smaller inputs are
+            /// checksummed on the calling thread.
+            pub fn checksum_parallel(&self, data: &[u8], threads: usize) -> $word {
+                checksum_parallel(*self, data, threads)
+            }
+
+            /// Compute the CRC of the file at `path` using up to `threads`
+            /// threads, each reading its own range of the file.
+            pub fn checksum_file_parallel<P: AsRef<Path>>(
+                &self,
+                path: P,
+                threads: usize,
+            ) -> io::Result<$word> {
+                checksum_file_parallel(*self, path.as_ref(), threads)
+            }
+        }
+    )*};
+}
+
+impl_parallel!(u8, u16, u32, u64);
+
+/// Number of threads to use for `len` bytes: at most `threads`, the available
+/// parallelism and one per [`MIN_CHUNK`] bytes, and at least one.
+fn thread_count(len: u64, threads: usize) -> usize {
+    let cores = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let parts = usize::try_from(len / MIN_CHUNK as u64).unwrap_or(usize::MAX);
+    threads.min(cores).min(parts).max(1)
+}
+
+fn checksum_parallel<W: Word + Send + Sync>(
+    params: Algorithm<W>,
+    data: &[u8],
+    threads: usize,
+) -> W {
+    let threads = thread_count(data.len() as u64, threads);
+    with_tables(params, |tables| {
+        if threads == 1 {
+            return checksum_with(tables, data);
+        }
+        checksum_chunks(tables, data, (data.len() + threads - 1) / threads)
+    })
+}
+
+/// Checksum each `chunk` bytes of `data` on its own thread.
+fn checksum_chunks<W: Word + Send + Sync>(tables: &CrcTables<W>, data: &[u8], chunk: usize) -> W {
+    thread::scope(|scope| {
+        let workers: Vec<_> = data
+            .chunks(chunk)
+            .map(|part| (part.len(), scope.spawn(move || checksum_with(tables, part))))
+            .collect();
+        merge(
+            tables.params(),
+            workers.into_iter().map(|(len, worker)| {
+                (worker.join().expect("checksum worker panicked"), len as u64)
+            }),
+        )
+    })
+}
+
+fn checksum_file_parallel<W: Word + Send + Sync>(
+    params: Algorithm<W>,
+    path: &Path,
+    threads: usize,
+) -> io::Result<W> {
+    let len = File::open(path)?.metadata()?.len();
+    let threads = thread_count(len, threads) as u64;
+    with_tables(params, |tables| {
+        if threads == 1 {
+            return checksum_range(tables, path, 0, len);
+        }
+        checksum_file_chunks(tables, path, len, (len + threads - 1) / threads)
+    })
+}
+
+/// Checksum each `chunk` bytes of the `len`-byte file at `path` on its own
+/// thread.
+fn checksum_file_chunks<W: Word + Send + Sync>(
+    tables: &CrcTables<W>,
+    path: &Path,
+    len: u64,
+    chunk: u64,
+) -> io::Result<W> {
+    let ranges = (0..len)
+        .step_by(chunk as usize)
+        .map(|start| (start, chunk.min(len - start)));
+    thread::scope(|scope| {
+        let workers: Vec<_> = ranges
+            .map(|(start, len)| {
+                let worker = scope.spawn(move || checksum_range(tables, path, start, len));
+                (worker, len)
+            })
+            .collect();
+        let mut partials = Vec::with_capacity(workers.len());
+        for (worker, len) in workers {
+            partials.push((worker.join().expect("checksum worker panicked")?, len));
+        }
+        Ok(merge(tables.params(), partials.into_iter()))
+    })
+}
+
+/// Checksum `len` bytes of the file at `path` starting at `start`.
+fn checksum_range<W: Word>(
+    tables: &CrcTables<W>,
+    path: &Path,
+    start: u64,
+    len: u64,
+) -> io::Result<W> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(start))?;
+    let mut reader = file.take(len);
+    let mut engine = CrcEngine::new(tables);
+    let mut buffer = vec![0u8; READ_BUFFER];
+    let mut remaining = len;
+    while remaining > 0 {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "file shrank while being checksummed",
+            ));
+        }
+        engine.update(&buffer[..read]);
+        remaining -= read as u64;
+    }
+    Ok(engine.finalize())
+}
+
+/// Fold in-order `(crc, len)` partials into the CRC of their concatenation.
+fn merge<W: Word>(params: Algorithm<W>, mut partials: impl Iterator<Item = (W, u64)>) -> W {
+    let (first, _) = partials.next().expect("at least one chunk");
+    partials.fold(first, |acc, (crc, len)| combine(params, acc, crc, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::{crc32, crc32c, Algorithm16, Crc32c, CRC32, CRC32C, CRC64_XZ};
+
+    fn sample(len: usize) -> Vec<u8> {
+        let mut state = 0x2545_F491u32;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let data = sample(3 * MIN_CHUNK + 12345);
+        for threads in [0, 1, 2, 3, 8] {
+            for len in [0, 1000, MIN_CHUNK, MIN_CHUNK + 1, data.len()] {
+                let input = &data[..len];
+                assert_eq!(CRC32.checksum_parallel(input, threads), crc32(input));
+                assert_eq!(CRC32C.checksum_parallel(input, threads), crc32c(input));
+                assert_eq!(
+                    CRC64_XZ.checksum_parallel(input, threads),
+                    CRC64_XZ.checksum(input)
+                );
+            }
+        }
+        let umts = Algorithm16::with_width("crc12-umts", 12, 0x80F, 0, 0, false, true);
+        assert_eq!(umts.checksum_parallel(&data, 4), umts.checksum(&data));
+
+        // The machine may not have enough cores to split, so drive the
+        // threaded path directly.
+        let umts_tables = umts.tables();
+        for chunk in [1000, MIN_CHUNK, data.len() - 1] {
+            assert_eq!(
+                checksum_chunks(Crc32c::tables(), &data, chunk),
+                crc32c(&data)
+            );
+            assert_eq!(
+                checksum_chunks(&umts_tables, &data, chunk),
+                umts.checksum(&data)
+            );
+        }
+    }
+
+    #[test]
+    fn thread_count_is_clamped() {
+        let cores = thread::available_parallelism().unwrap().get();
+        assert_eq!(thread_count(0, 64), 1);
+        assert_eq!(thread_count(MIN_CHUNK as u64 - 1, 64), 1);
+        assert_eq!(thread_count(u64::MAX, 0), 1);
+        assert_eq!(thread_count(u64::MAX, usize::MAX), cores);
+        assert_eq!(thread_count(3 * MIN_CHUNK as u64, 1000), cores.min(3));
+    }
+
+    #[test]
+    fn parallel_file_matches_sequential() {
+        let data = sample(5 * MIN_CHUNK + 7);
+        let path = std::env::temp_dir().join(format!("fastcrc-parallel-{}", std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(&data).unwrap();
+        drop(file);
+
+        for threads in [1, 2, 4, 16] {
+            assert_eq!(
+                CRC32C.checksum_file_parallel(&path, threads).unwrap(),
+                crc32c(&data)
+            );
+        }
+        for chunk in [
+            MIN_CHUNK as u64,
+            2 * MIN_CHUNK as u64,
+            data.len() as u64 - 1,
+        ] {
+            assert_eq!(
+                checksum_file_chunks(Crc32c::tables(), &path, data.len() as u64, chunk).unwrap(),
+                crc32c(&data)
+            );
+        }
+        std::fs::write(&path, b"").unwrap();
+        assert_eq!(CRC32.checksum_file_parallel(&path, 4).unwrap(), crc32(b""));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
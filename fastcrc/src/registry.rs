@@ -0,0 +1,476 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime lookup of algorithms by their RevEng catalogue name or alias.
+//!
+//! Names are matched ignoring ASCII case, `-`, `_` and spaces, so `crc32c`,
+//! `CRC-32C` and `crc_32c` all resolve to CRC-32/ISCSI.
+
+use core::fmt;
+
+use crate::core::Checksum;
+use crate::crc16::*;
+use crate::crc32::{
+    Crc32, Crc32Aixm, Crc32Autosar, Crc32Base91D, Crc32Bzip2, Crc32CdRomEdc, Crc32Cksum,
+    Crc32Jamcrc, Crc32Mef, Crc32Mpeg2, Crc32Xfer, CRC32, CRC32_AIXM, CRC32_AUTOSAR, CRC32_BASE91_D,
+    CRC32_BZIP2, CRC32_CD_ROM_EDC, CRC32_CKSUM, CRC32_JAMCRC, CRC32_MEF, CRC32_MPEG_2, CRC32_XFER,
+};
+use crate::crc32c::{Crc32c, CRC32C};
+use crate::crc64::*;
+use crate::crc8::*;
+use crate::{Algorithm16, Algorithm32, Algorithm64, Algorithm8};
+
+/// An algorithm description of any register width.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnyAlgorithm {
+    /// A CRC of up to 8 bits.
+    Crc8(Algorithm8),
+    /// A CRC of up to 16 bits.
+    Crc16(Algorithm16),
+    /// A CRC of up to 32 bits.
+    Crc32(Algorithm32),
+    /// A CRC of up to 64 bits.
+    Crc64(Algorithm64),
+}
+
+impl AnyAlgorithm {
+    /// Human friendly name of the algorithm.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AnyAlgorithm::Crc8(params) => params.name,
+            AnyAlgorithm::Crc16(params) => params.name,
+            AnyAlgorithm::Crc32(params) => params.name,
+            AnyAlgorithm::Crc64(params) => params.name,
+        }
+    }
+
+    /// CRC width in bits.
+    pub fn width(&self) -> u8 {
+        match self {
+            AnyAlgorithm::Crc8(params) => params.width,
+            AnyAlgorithm::Crc16(params) => params.width,
+            AnyAlgorithm::Crc32(params) => params.width,
+            AnyAlgorithm::Crc64(params) => params.width,
+        }
+    }
+
+    /// Compute the CRC of `data` in one shot, widened to `u64`.
+    pub fn checksum(&self, data: &[u8]) -> u64 {
+        match self {
+            AnyAlgorithm::Crc8(params) => params.checksum(data).into(),
+            AnyAlgorithm::Crc16(params) => params.checksum(data).into(),
+            AnyAlgorithm::Crc32(params) => params.checksum(data).into(),
+            AnyAlgorithm::Crc64(params) => params.checksum(data),
+        }
+    }
+}
+
+/// Object-safe streaming digest returned by the registry.
+///
+/// Every digest implementing [`Checksum`] with a register of at most 64 bits
+/// implements this trait.
+pub trait DynChecksum: Send + Sync {
+    /// Feed `data` into the digest.
+    fn update(&mut self, data: &[u8]);
+
+    /// Reset the digest to its initial value.
+    fn reset(&mut self);
+
+    /// Return the CRC of the bytes fed so far, widened to `u64`.
+    fn checksum(&self) -> u64;
+
+    /// Clone the digest into a new box.
+    fn box_clone(&self) -> Box<dyn DynChecksum>;
+}
+
+impl<D> DynChecksum for D
+where
+    D: Checksum + Clone + Send + Sync + 'static,
+    D::Value: Into<u64>,
+{
+    fn update(&mut self, data: &[u8]) {
+        Checksum::update(self, data);
+    }
+
+    fn reset(&mut self) {
+        Checksum::reset(self);
+    }
+
+    fn checksum(&self) -> u64 {
+        Checksum::checksum(self).into()
+    }
+
+    fn box_clone(&self) -> Box<dyn DynChecksum> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn DynChecksum> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+impl fmt::Debug for dyn DynChecksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynChecksum")
+            .field("checksum", &self.checksum())
+            .finish()
+    }
+}
+
+/// An algorithm together with the names it is registered under and a
+/// constructor for its digest.
+#[derive(Clone, Copy)]
+pub struct Entry {
+    algorithm: AnyAlgorithm,
+    names: &'static [&'static str],
+    digest: fn() -> Box<dyn DynChecksum>,
+}
+
+impl Entry {
+    /// Describe `algorithm`, computed by the digest type `D`, under `names`;
+    /// the first name is the canonical one.
+    pub const fn new<D>(algorithm: AnyAlgorithm, names: &'static [&'static str]) -> Self
+    where
+        D: DynChecksum + Default + 'static,
+    {
+        Self {
+            algorithm,
+            names,
+            digest: new_digest::<D>,
+        }
+    }
+
+    /// The algorithm described by this entry.
+    pub fn algorithm(&self) -> AnyAlgorithm {
+        self.algorithm
+    }
+
+    /// The canonical name of the algorithm.
+    pub fn name(&self) -> &'static str {
+        self.names.first().copied().unwrap_or(self.algorithm.name())
+    }
+
+    /// Every name the algorithm is registered under.
+    pub fn names(&self) -> &'static [&'static str] {
+        self.names
+    }
+
+    /// Create a new digest computing the algorithm.
+    pub fn digest(&self) -> Box<dyn DynChecksum> {
+        (self.digest)()
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        self.names
+            .iter()
+            .any(|candidate| names_match(candidate, name))
+    }
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("algorithm", &self.algorithm)
+            .field("names", &self.names)
+            .finish()
+    }
+}
+
+fn new_digest<D: DynChecksum + Default + 'static>() -> Box<dyn DynChecksum> {
+    Box::new(D::default())
+}
+
+/// Compare two algorithm names ignoring ASCII case, `-`, `_` and spaces.
+fn names_match(a: &str, b: &str) -> bool {
+    fn normalize(name: &str) -> impl Iterator<Item = u8> + '_ {
+        name.bytes()
+            .filter(|b| !matches!(b, b'-' | b'_' | b' '))
+            .map(|b| b.to_ascii_lowercase())
+    }
+    normalize(a).eq(normalize(b))
+}
+
+/// A set of named algorithms, starting from the built-in catalogue.
+///
+/// ```
+/// use fastcrc::Registry;
+///
+/// let registry = Registry::builtin();
+/// let mut digest = registry.digest("CRC-32/ISCSI").unwrap();
+/// digest.update(b"123456789");
+/// assert_eq!(digest.checksum(), 0xE306_9283);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry holding every built-in algorithm.
+    pub fn builtin() -> Self {
+        Self {
+            entries: CATALOGUE.to_vec(),
+        }
+    }
+
+    /// Add `entry`; it takes precedence over earlier entries sharing a name.
+    pub fn register(&mut self, entry: Entry) {
+        self.entries.push(entry);
+    }
+
+    /// Find the entry registered under `name`.
+    pub fn get(&self, name: &str) -> Option<&Entry> {
+        self.entries.iter().rev().find(|entry| entry.matches(name))
+    }
+
+    /// Create a digest for the algorithm registered under `name`.
+    pub fn digest(&self, name: &str) -> Option<Box<dyn DynChecksum>> {
+        self.get(name).map(Entry::digest)
+    }
+
+    /// Iterate over the registered entries in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter()
+    }
+}
+
+/// Find the built-in algorithm registered under `name`.
+pub fn lookup(name: &str) -> Option<&'static Entry> {
+    CATALOGUE.iter().find(|entry| entry.matches(name))
+}
+
+/// Every built-in algorithm with its RevEng name and common aliases.
+pub(crate) static CATALOGUE: &[Entry] = &[
+    Entry::new::<Crc8Smbus>(AnyAlgorithm::Crc8(CRC8_SMBUS), &["CRC-8/SMBUS", "CRC-8"]),
+    Entry::new::<Crc8MaximDow>(
+        AnyAlgorithm::Crc8(CRC8_MAXIM_DOW),
+        &["CRC-8/MAXIM-DOW", "CRC-8/MAXIM", "DOW-CRC"],
+    ),
+    Entry::new::<Crc8Autosar>(AnyAlgorithm::Crc8(CRC8_AUTOSAR), &["CRC-8/AUTOSAR"]),
+    Entry::new::<Crc8SaeJ1850>(AnyAlgorithm::Crc8(CRC8_SAE_J1850), &["CRC-8/SAE-J1850"]),
+    Entry::new::<Crc8Bluetooth>(AnyAlgorithm::Crc8(CRC8_BLUETOOTH), &["CRC-8/BLUETOOTH"]),
+    Entry::new::<Crc8DvbS2>(AnyAlgorithm::Crc8(CRC8_DVB_S2), &["CRC-8/DVB-S2"]),
+    Entry::new::<Crc16Modbus>(
+        AnyAlgorithm::Crc16(CRC16_MODBUS),
+        &["CRC-16/MODBUS", "MODBUS"],
+    ),
+    Entry::new::<Crc16IbmSdlc>(
+        AnyAlgorithm::Crc16(CRC16_IBM_SDLC),
+        &[
+            "CRC-16/IBM-SDLC",
+            "CRC-16/ISO-HDLC",
+            "CRC-16/ISO-IEC-14443-3-B",
+            "CRC-16/X-25",
+            "CRC-B",
+            "X-25",
+        ],
+    ),
+    Entry::new::<Crc16Xmodem>(
+        AnyAlgorithm::Crc16(CRC16_XMODEM),
+        &[
+            "CRC-16/XMODEM",
+            "CRC-16/ACORN",
+            "CRC-16/LTE",
+            "CRC-16/V-41-MSB",
+            "XMODEM",
+            "ZMODEM",
+        ],
+    ),
+    Entry::new::<Crc16Kermit>(
+        AnyAlgorithm::Crc16(CRC16_KERMIT),
+        &[
+            "CRC-16/KERMIT",
+            "CRC-16/BLUETOOTH",
+            "CRC-16/CCITT",
+            "CRC-16/CCITT-TRUE",
+            "CRC-16/V-41-LSB",
+            "CRC-CCITT",
+            "KERMIT",
+        ],
+    ),
+    Entry::new::<Crc16Ibm3740>(
+        AnyAlgorithm::Crc16(CRC16_IBM_3740),
+        &["CRC-16/IBM-3740", "CRC-16/AUTOSAR", "CRC-16/CCITT-FALSE"],
+    ),
+    Entry::new::<Crc16Arc>(
+        AnyAlgorithm::Crc16(CRC16_ARC),
+        &["CRC-16/ARC", "ARC", "CRC-16", "CRC-16/LHA", "CRC-IBM"],
+    ),
+    Entry::new::<Crc16Usb>(AnyAlgorithm::Crc16(CRC16_USB), &["CRC-16/USB"]),
+    Entry::new::<Crc16T10Dif>(AnyAlgorithm::Crc16(CRC16_T10_DIF), &["CRC-16/T10-DIF"]),
+    Entry::new::<Crc16Dnp>(AnyAlgorithm::Crc16(CRC16_DNP), &["CRC-16/DNP"]),
+    Entry::new::<Crc16Genibus>(
+        AnyAlgorithm::Crc16(CRC16_GENIBUS),
+        &[
+            "CRC-16/GENIBUS",
+            "CRC-16/DARC",
+            "CRC-16/EPC",
+            "CRC-16/EPC-C1G2",
+            "CRC-16/I-CODE",
+        ],
+    ),
+    Entry::new::<Crc32>(
+        AnyAlgorithm::Crc32(CRC32),
+        &[
+            "CRC-32/ISO-HDLC",
+            "CRC-32",
+            "CRC-32/ADCCP",
+            "CRC-32/V-42",
+            "CRC-32/XZ",
+            "PKZIP",
+        ],
+    ),
+    Entry::new::<Crc32c>(
+        AnyAlgorithm::Crc32(CRC32C),
+        &[
+            "CRC-32/ISCSI",
+            "CRC-32C",
+            "CRC-32/BASE91-C",
+            "CRC-32/CASTAGNOLI",
+            "CRC-32/INTERLAKEN",
+        ],
+    ),
+    Entry::new::<Crc32Bzip2>(
+        AnyAlgorithm::Crc32(CRC32_BZIP2),
+        &["CRC-32/BZIP2", "CRC-32/AAL5", "CRC-32/DECT-B", "B-CRC-32"],
+    ),
+    Entry::new::<Crc32Mpeg2>(AnyAlgorithm::Crc32(CRC32_MPEG_2), &["CRC-32/MPEG-2"]),
+    Entry::new::<Crc32Cksum>(
+        AnyAlgorithm::Crc32(CRC32_CKSUM),
+        &["CRC-32/CKSUM", "CKSUM", "CRC-32/POSIX"],
+    ),
+    Entry::new::<Crc32Jamcrc>(
+        AnyAlgorithm::Crc32(CRC32_JAMCRC),
+        &["CRC-32/JAMCRC", "JAMCRC"],
+    ),
+    Entry::new::<Crc32Xfer>(AnyAlgorithm::Crc32(CRC32_XFER), &["CRC-32/XFER", "XFER"]),
+    Entry::new::<Crc32Autosar>(AnyAlgorithm::Crc32(CRC32_AUTOSAR), &["CRC-32/AUTOSAR"]),
+    Entry::new::<Crc32Aixm>(AnyAlgorithm::Crc32(CRC32_AIXM), &["CRC-32/AIXM", "CRC-32Q"]),
+    Entry::new::<Crc32Base91D>(
+        AnyAlgorithm::Crc32(CRC32_BASE91_D),
+        &["CRC-32/BASE91-D", "CRC-32D"],
+    ),
+    Entry::new::<Crc32CdRomEdc>(
+        AnyAlgorithm::Crc32(CRC32_CD_ROM_EDC),
+        &["CRC-32/CD-ROM-EDC"],
+    ),
+    Entry::new::<Crc32Mef>(AnyAlgorithm::Crc32(CRC32_MEF), &["CRC-32/MEF"]),
+    Entry::new::<Crc64Xz>(
+        AnyAlgorithm::Crc64(CRC64_XZ),
+        &["CRC-64/XZ", "CRC-64/GO-ECMA"],
+    ),
+    Entry::new::<Crc64Ecma182>(
+        AnyAlgorithm::Crc64(CRC64_ECMA_182),
+        &["CRC-64/ECMA-182", "CRC-64"],
+    ),
+    Entry::new::<Crc64GoIso>(AnyAlgorithm::Crc64(CRC64_GO_ISO), &["CRC-64/GO-ISO"]),
+    Entry::new::<Crc64Nvme>(AnyAlgorithm::Crc64(CRC64_NVME), &["CRC-64/NVME"]),
+    Entry::new::<Crc64We>(AnyAlgorithm::Crc64(CRC64_WE), &["CRC-64/WE"]),
+    Entry::new::<Crc64Redis>(AnyAlgorithm::Crc64(CRC64_REDIS), &["CRC-64/REDIS"]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crc16_xmodem, Crc32Algorithm, GenericCrc32};
+
+    #[test]
+    fn lookup_by_name_and_alias() {
+        for name in ["CRC-32/ISO-HDLC", "crc32", "CRC-32", "pkzip"] {
+            assert_eq!(
+                lookup(name).unwrap().algorithm(),
+                AnyAlgorithm::Crc32(CRC32)
+            );
+        }
+        for name in ["CRC-32C", "CRC-32/ISCSI", "crc32c", "crc_32/castagnoli"] {
+            assert_eq!(lookup(name).unwrap().name(), "CRC-32/ISCSI");
+        }
+        assert_eq!(lookup("ZMODEM").unwrap().algorithm().width(), 16);
+        assert!(lookup("CRC-32/UNKNOWN").is_none());
+    }
+
+    #[test]
+    fn catalogue_digests_match_algorithms() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        for entry in CATALOGUE {
+            let algorithm = entry.algorithm();
+            let mut digest = entry.digest();
+            digest.update(&data[..10]);
+            let mut copy = digest.clone();
+            digest.update(&data[10..]);
+            assert_eq!(
+                digest.checksum(),
+                algorithm.checksum(data),
+                "{}",
+                entry.name()
+            );
+
+            copy.reset();
+            copy.update(data);
+            assert_eq!(
+                copy.checksum(),
+                algorithm.checksum(data),
+                "{}",
+                entry.name()
+            );
+        }
+    }
+
+    #[test]
+    fn catalogue_names_are_unique() {
+        for (i, entry) in CATALOGUE.iter().enumerate() {
+            for name in entry.names() {
+                let owners: Vec<_> = CATALOGUE
+                    .iter()
+                    .filter(|other| other.matches(name))
+                    .collect();
+                assert_eq!(owners.len(), 1, "entry {i}: {name}");
+            }
+        }
+    }
+
+    #[test]
+    fn registered_entries_take_precedence() {
+        struct Mpeg2;
+
+        impl Crc32Algorithm for Mpeg2 {
+            const ALGORITHM: Algorithm32 = CRC32_MPEG_2;
+        }
+
+        let mut registry = Registry::builtin();
+        registry.register(Entry::new::<GenericCrc32<Mpeg2>>(
+            AnyAlgorithm::Crc32(CRC32_MPEG_2),
+            &["CRC-32/MPEG-2", "CRC-32"],
+        ));
+        let mut digest = registry.digest("crc-32/mpeg-2").unwrap();
+        digest.update(b"123456789");
+        assert_eq!(digest.checksum(), 0x0376_E6E7);
+        assert_eq!(registry.get("CRC-32").unwrap().name(), "CRC-32/MPEG-2");
+        assert_eq!(
+            registry
+                .get("xmodem")
+                .unwrap()
+                .algorithm()
+                .checksum(b"123456789"),
+            u64::from(crc16_xmodem(b"123456789"))
+        );
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rolling CRC over a fixed-size window.
+//!
+//! By linearity, the register after a window equals the contributions of its
+//! bytes, each advanced over the bytes that follow it, plus the contribution
+//! of `init` advanced over the whole window. Sliding the window by one byte
+//! therefore feeds the new byte with a regular table step and cancels the
+//! oldest byte with a second table, indexed by that byte, holding its
+//! contribution after a full window together with the correction of `init`.
+
+use crate::core::{finalize_value, fmt_digest, update_byte, Algorithm32};
+
+/// CRC of the last `window` bytes fed, updated in O(1) per byte.
+///
+/// ```
+/// use fastcrc::{crc32, RollingCrc32, CRC32};
+///
+/// let data = b"The quick brown fox jumps over the lazy dog";
+/// let mut rolling = RollingCrc32::new(CRC32, 16);
+/// for (end, &byte) in data.iter().enumerate() {
+///     rolling.push(byte);
+///     let start = (end + 1).saturating_sub(16);
+///     assert_eq!(rolling.checksum(), crc32(&data[start..=end]));
+/// }
+/// ```
+#[derive(Clone)]
+pub struct RollingCrc32 {
+    params: Algorithm32,
+    table: [u32; 256],
+    outgoing: [u32; 256],
+    init: u32,
+    state: u32,
+    window: Vec<u8>,
+    window_len: usize,
+    /// Index of the oldest byte once the window is full.
+    head: usize,
+}
+
+impl RollingCrc32 {
+    /// Create a rolling CRC of `params` over windows of `window` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero.
+    pub fn new(params: Algorithm32, window: usize) -> Self {
+        assert!(window > 0, "rolling window must not be empty");
+        let table = params.table();
+        let init = params.register_init();
+        let reflected = params.reflect_in;
+
+        // The oldest byte has `window` bytes fed after it when it leaves.
+        let advance = |mut state: u32| {
+            for _ in 0..window {
+                state = update_byte(state, &table, reflected, 0);
+            }
+            state
+        };
+        let init_correction = advance(update_byte(init, &table, reflected, 0)) ^ advance(init);
+        let mut basis = [0u32; 8];
+        for (bit, contribution) in basis.iter_mut().enumerate() {
+            *contribution = advance(update_byte(0, &table, reflected, 1 << bit));
+        }
+        let mut outgoing = [0u32; 256];
+        for (byte, entry) in outgoing.iter_mut().enumerate() {
+            *entry = basis
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| byte >> bit & 1 != 0)
+                .fold(init_correction, |acc, (_, contribution)| acc ^ contribution);
+        }
+
+        Self {
+            params,
+            table,
+            outgoing,
+            init,
+            state: init,
+            window: Vec::with_capacity(window),
+            window_len: window,
+            head: 0,
+        }
+    }
+
+    /// Slide the window forward by `byte`, dropping the oldest byte once the
+    /// window is full.
+    pub fn push(&mut self, byte: u8) {
+        self.state = update_byte(self.state, &self.table, self.params.reflect_in, byte);
+        if self.window.len() < self.window_len {
+            self.window.push(byte);
+        } else {
+            let oldest = core::mem::replace(&mut self.window[self.head], byte);
+            self.state ^= self.outgoing[usize::from(oldest)];
+            self.head = (self.head + 1) % self.window_len;
+        }
+    }
+
+    /// Push every byte of `data`.
+    pub fn extend(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.push(byte);
+        }
+    }
+
+    /// Return the CRC of the bytes currently in the window.
+    pub fn checksum(&self) -> u32 {
+        finalize_value(self.state, self.params)
+    }
+
+    /// The window length in bytes.
+    pub fn window_len(&self) -> usize {
+        self.window_len
+    }
+
+    /// Whether a full window of bytes has been fed.
+    pub fn is_full(&self) -> bool {
+        self.window.len() == self.window_len
+    }
+
+    /// Empty the window.
+    pub fn reset(&mut self) {
+        self.state = self.init;
+        self.window.clear();
+        self.head = 0;
+    }
+}
+
+impl core::fmt::Debug for RollingCrc32 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_digest(f, "RollingCrc32", &self.params, self.checksum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CRC32, CRC32C, CRC32_BZIP2};
+
+    fn sample_data() -> Vec<u8> {
+        (0..1000u32)
+            .map(|i| (i.wrapping_mul(131) >> 3) as u8)
+            .collect()
+    }
+
+    fn check_rolling(params: Algorithm32, window: usize) {
+        let data = sample_data();
+        let mut rolling = RollingCrc32::new(params, window);
+        for (end, &byte) in data.iter().enumerate() {
+            rolling.push(byte);
+            let start = (end + 1).saturating_sub(window);
+            assert_eq!(
+                rolling.checksum(),
+                params.checksum(&data[start..=end]),
+                "{}: window {window} ending at {end}",
+                params.name
+            );
+        }
+        assert!(rolling.is_full());
+    }
+
+    #[test]
+    fn rolling_matches_window_checksum() {
+        for window in [1, 4, 48, 300] {
+            check_rolling(CRC32, window);
+            check_rolling(CRC32C, window);
+            check_rolling(CRC32_BZIP2, window);
+        }
+        check_rolling(
+            Algorithm32::with_width("crc24-openpgp", 24, 0x864CFB, 0xB704CE, 0, false, false),
+            32,
+        );
+        check_rolling(
+            Algorithm32::with_width("crc17-mixed", 17, 0x1685B, 0x1234, 0x1F0F0, true, false),
+            7,
+        );
+    }
+
+    #[test]
+    fn reset_empties_the_window() {
+        let data = sample_data();
+        let mut rolling = RollingCrc32::new(CRC32C, 64);
+        rolling.extend(&data[..100]);
+        rolling.reset();
+        assert!(!rolling.is_full());
+        assert_eq!(rolling.checksum(), CRC32C.checksum(b""));
+        rolling.extend(&data[100..200]);
+        assert_eq!(rolling.checksum(), CRC32C.checksum(&data[136..200]));
+    }
+
+    #[test]
+    fn debug_shows_checksum() {
+        let mut rolling = RollingCrc32::new(CRC32C, 9);
+        rolling.extend(b"abc123456789");
+        assert_eq!(
+            format!("{rolling:?}"),
+            r#"RollingCrc32 { algorithm: "crc32c", checksum: 0xe3069283 }"#
+        );
+    }
+}
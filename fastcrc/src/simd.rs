@@ -0,0 +1,360 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hardware-accelerated CRC backends for x86_64.
+//!
+//! Every backend is represented by a zero-sized token that can only be
+//! obtained after runtime CPU feature detection succeeded, which keeps the
+//! `unsafe` calls into `#[target_feature]` functions contained in this module.
+//! Per-algorithm constants are computed at compile time and stored next to
+//! the lookup tables, so detection is repeated (from the standard library's
+//! cache) on every update instead of being stored in the engine.
+
+use std::arch::x86_64::{
+    __m128i, _mm_and_si128, _mm_clmulepi64_si128, _mm_crc32_u64, _mm_crc32_u8, _mm_cvtsi32_si128,
+    _mm_extract_epi32, _mm_loadu_si128, _mm_set_epi32, _mm_set_epi64x, _mm_srli_si128,
+    _mm_xor_si128,
+};
+
+use crate::core::{Accelerator, Algorithm32};
+
+/// Hardware backends applicable to a CRC32 algorithm.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Accel32 {
+    castagnoli: bool,
+    pclmul: Option<PclmulKeys>,
+}
+
+impl Accel32 {
+    /// Select the backends able to compute `params`, independently of the
+    /// running CPU.
+    pub(crate) const fn new(params: &Algorithm32) -> Self {
+        let reflected = params.width == 32 && params.reflect_in;
+        Self {
+            castagnoli: reflected && params.polynomial == CASTAGNOLI_POLYNOMIAL,
+            pclmul: if reflected {
+                Some(PclmulKeys::new(params.polynomial))
+            } else {
+                None
+            },
+        }
+    }
+}
+
+impl Accelerator<u32> for Accel32 {
+    fn update<'a>(&self, state: u32, data: &'a [u8]) -> (u32, &'a [u8]) {
+        let (state, data) = match (&self.pclmul, Pclmul::detect()) {
+            (Some(keys), Some(pclmul)) => pclmul.update(keys, state, data),
+            _ => (state, data),
+        };
+        match Sse42::detect() {
+            Some(sse42) if self.castagnoli => (sse42.update(state, data), &[]),
+            _ => (state, data),
+        }
+    }
+}
+
+/// Castagnoli polynomial implemented by the SSE4.2 `crc32` instruction.
+const CASTAGNOLI_POLYNOMIAL: u32 = 0x1EDC6F41;
+
+/// Proof that the running CPU supports the SSE4.2 `crc32` instruction.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Sse42(());
+
+impl Sse42 {
+    /// Return a token if the running CPU supports SSE4.2.
+    pub(crate) fn detect() -> Option<Self> {
+        is_x86_feature_detected!("sse4.2").then_some(Self(()))
+    }
+
+    /// Feed `data` into the reflected CRC32C register `state`.
+    pub(crate) fn update(self, state: u32, data: &[u8]) -> u32 {
+        // SAFETY: the token is only constructed after SSE4.2 was detected.
+        unsafe { update_sse42(state, data) }
+    }
+}
+
+#[target_feature(enable = "sse4.2")]
+unsafe fn update_sse42(state: u32, data: &[u8]) -> u32 {
+    let mut words = data.chunks_exact(8);
+    let mut crc = u64::from(state);
+    for word in &mut words {
+        let word = u64::from_le_bytes(word.try_into().expect("chunk is 8 bytes"));
+        crc = _mm_crc32_u64(crc, word);
+    }
+    let mut crc = crc as u32;
+    for &byte in words.remainder() {
+        crc = _mm_crc32_u8(crc, byte);
+    }
+    crc
+}
+
+/// Folding constants for a reflected CRC32 polynomial, in the bit-reflected
+/// layout expected by the PCLMULQDQ folding loop.
+///
+/// The derivation follows Intel's "Fast CRC Computation for Generic
+/// Polynomials Using PCLMULQDQ Instruction" white paper.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PclmulKeys {
+    /// `x^(512+32) mod P` and `x^(512-32) mod P`, folding across 4 lanes.
+    k1: u64,
+    k2: u64,
+    /// `x^(128+32) mod P` and `x^(128-32) mod P`, folding a single lane.
+    k3: u64,
+    k4: u64,
+    /// `x^64 mod P`, reducing 96 bits to 64 bits.
+    k5: u64,
+    /// The full 33-bit polynomial.
+    p: u64,
+    /// `floor(x^64 / P)`, the Barrett reduction multiplier.
+    mu: u64,
+}
+
+impl PclmulKeys {
+    const fn new(polynomial: u32) -> Self {
+        Self {
+            k1: fold_key(4 * 128 + 32, polynomial),
+            k2: fold_key(4 * 128 - 32, polynomial),
+            k3: fold_key(128 + 32, polynomial),
+            k4: fold_key(128 - 32, polynomial),
+            k5: fold_key(64, polynomial),
+            p: ((polynomial.reverse_bits() as u64) << 1) | 1,
+            mu: x64_div_p(polynomial).reverse_bits() >> (64 - 33),
+        }
+    }
+}
+
+/// Proof that the running CPU supports PCLMULQDQ and SSE4.1.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Pclmul(());
+
+impl Pclmul {
+    /// Smallest input folded by [`Pclmul::update`]; shorter inputs are left to
+    /// the table engine.
+    const MIN_LEN: usize = 128;
+
+    /// Return a token if the running CPU supports PCLMULQDQ and SSE4.1.
+    pub(crate) fn detect() -> Option<Self> {
+        let supported = is_x86_feature_detected!("pclmulqdq") && is_x86_feature_detected!("sse4.1");
+        supported.then_some(Self(()))
+    }
+
+    /// Fold the 16-byte aligned prefix of `data` into the reflected register
+    /// `state`, returning the new register and the unprocessed tail.
+    pub(crate) fn update<'a>(
+        self,
+        keys: &PclmulKeys,
+        state: u32,
+        data: &'a [u8],
+    ) -> (u32, &'a [u8]) {
+        if data.len() < Self::MIN_LEN {
+            return (state, data);
+        }
+        let split = data.len() - data.len() % 16;
+        let (head, tail) = data.split_at(split);
+        // SAFETY: the token is only constructed after PCLMULQDQ and SSE4.1
+        // were detected.
+        let state = unsafe { fold_pclmul(keys, state, head) };
+        (state, tail)
+    }
+}
+
+/// Compute the reflected folding key `x^n mod P`, shifted for PCLMULQDQ.
+const fn fold_key(n: u32, polynomial: u32) -> u64 {
+    (xn_mod_p(n, polynomial).reverse_bits() as u64) << 1
+}
+
+/// Compute `x^n mod P` for the 33-bit polynomial `x^32 + polynomial`.
+const fn xn_mod_p(n: u32, polynomial: u32) -> u32 {
+    let mut rem = 1u32;
+    let mut i = 0;
+    while i < n {
+        let carry = rem & 0x8000_0000 != 0;
+        rem <<= 1;
+        if carry {
+            rem ^= polynomial;
+        }
+        i += 1;
+    }
+    rem
+}
+
+/// Compute `floor(x^64 / P)` for the 33-bit polynomial `x^32 + polynomial`.
+const fn x64_div_p(polynomial: u32) -> u64 {
+    let full = (1u128 << 32) | polynomial as u128;
+    let mut rem = 1u128 << 64;
+    let mut quotient = 0u64;
+    let mut shift = 33;
+    while shift > 0 {
+        shift -= 1;
+        if rem & (1u128 << (shift + 32)) != 0 {
+            rem ^= full << shift;
+            quotient |= 1 << shift;
+        }
+    }
+    quotient
+}
+
+#[target_feature(enable = "pclmulqdq", enable = "sse4.1")]
+unsafe fn fold_pclmul(k: &PclmulKeys, state: u32, data: &[u8]) -> u32 {
+    debug_assert!(data.len() >= Pclmul::MIN_LEN && data.len() % 16 == 0);
+    let mut blocks = data.chunks_exact(16).map(|block| {
+        // SAFETY: every block is exactly 16 bytes long.
+        unsafe { _mm_loadu_si128(block.as_ptr().cast()) }
+    });
+    let mut next = || blocks.next().expect("enough blocks");
+
+    let mut x3 = _mm_xor_si128(next(), _mm_cvtsi32_si128(state as i32));
+    let mut x2 = next();
+    let mut x1 = next();
+    let mut x0 = next();
+    let mut remaining = data.len() / 16 - 4;
+
+    let k1k2 = _mm_set_epi64x(k.k2 as i64, k.k1 as i64);
+    while remaining >= 4 {
+        x3 = fold_block(x3, next(), k1k2);
+        x2 = fold_block(x2, next(), k1k2);
+        x1 = fold_block(x1, next(), k1k2);
+        x0 = fold_block(x0, next(), k1k2);
+        remaining -= 4;
+    }
+
+    let k3k4 = _mm_set_epi64x(k.k4 as i64, k.k3 as i64);
+    let mut x = fold_block(x3, x2, k3k4);
+    x = fold_block(x, x1, k3k4);
+    x = fold_block(x, x0, k3k4);
+    for _ in 0..remaining {
+        x = fold_block(x, next(), k3k4);
+    }
+
+    // Reduce 128 bits to 64 bits, multiplied by x^32.
+    let low32 = _mm_set_epi32(0, 0, 0, !0);
+    x = _mm_xor_si128(_mm_clmulepi64_si128(x, k3k4, 0x10), _mm_srli_si128(x, 8));
+    x = _mm_xor_si128(
+        _mm_clmulepi64_si128(
+            _mm_and_si128(x, low32),
+            _mm_set_epi64x(0, k.k5 as i64),
+            0x00,
+        ),
+        _mm_srli_si128(x, 4),
+    );
+
+    // Barrett reduction down to 32 bits.
+    let pu = _mm_set_epi64x(k.mu as i64, k.p as i64);
+    let t1 = _mm_clmulepi64_si128(_mm_and_si128(x, low32), pu, 0x10);
+    let t2 = _mm_clmulepi64_si128(_mm_and_si128(t1, low32), pu, 0x00);
+    _mm_extract_epi32(_mm_xor_si128(x, t2), 1) as u32
+}
+
+#[target_feature(enable = "pclmulqdq", enable = "sse4.1")]
+unsafe fn fold_block(acc: __m128i, block: __m128i, keys: __m128i) -> __m128i {
+    let lo = _mm_clmulepi64_si128(acc, keys, 0x00);
+    let hi = _mm_clmulepi64_si128(acc, keys, 0x11);
+    _mm_xor_si128(_mm_xor_si128(block, lo), hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::reflect_bits;
+
+    const CASTAGNOLI: Algorithm32 =
+        Algorithm32::new("crc32c", 0x1EDC6F41, 0xFFFF_FFFF, 0xFFFF_FFFF, true, true);
+
+    #[test]
+    fn sse42_matches_bitwise_reference() {
+        let Some(sse42) = Sse42::detect() else {
+            return;
+        };
+        assert_eq!(!sse42.update(!0, b"123456789"), 0xE306_9283);
+
+        let data: Vec<u8> = (0..1031u32)
+            .map(|i| (i.wrapping_mul(131) >> 2) as u8)
+            .collect();
+        // Unaligned starts and every short length exercise both the
+        // eight-byte loop and the byte-wise tail.
+        for offset in 0..8 {
+            for len in 0..=300 {
+                let input = &data[offset..offset + len];
+                assert_eq!(
+                    sse42.update(0x89AB_CDEF, input),
+                    update_bitwise(0x89AB_CDEF, CASTAGNOLI_POLYNOMIAL, input),
+                    "offset: {offset}, len: {len}"
+                );
+            }
+        }
+        assert_eq!(
+            sse42.update(0x89AB_CDEF, &data),
+            update_bitwise(0x89AB_CDEF, CASTAGNOLI_POLYNOMIAL, &data)
+        );
+    }
+
+    #[test]
+    fn pclmul_constants_match_reference() {
+        // Published constants for the IEEE polynomial.
+        let k = PclmulKeys::new(0x04C11DB7);
+        assert_eq!(k.k1, 0x1_5444_2BD4);
+        assert_eq!(k.k2, 0x1_C6E4_1596);
+        assert_eq!(k.k3, 0x1_7519_97D0);
+        assert_eq!(k.k4, 0x0_CCAA_009E);
+        assert_eq!(k.k5, 0x1_63CD_6124);
+        assert_eq!(k.p, 0x1_DB71_0641);
+        assert_eq!(k.mu, 0x1_F701_1641);
+    }
+
+    /// Bit-at-a-time reference update of a reflected register.
+    fn update_bitwise(mut state: u32, polynomial: u32, data: &[u8]) -> u32 {
+        let reflected = reflect_bits(polynomial, 32);
+        for &byte in data {
+            state ^= u32::from(byte);
+            for _ in 0..8 {
+                state = if state & 1 != 0 {
+                    (state >> 1) ^ reflected
+                } else {
+                    state >> 1
+                };
+            }
+        }
+        state
+    }
+
+    #[test]
+    fn pclmul_matches_bitwise_reference() {
+        let data: Vec<u8> = (0..4099u32)
+            .map(|i| (i.wrapping_mul(197) >> 3) as u8)
+            .collect();
+        let Some(pclmul) = Pclmul::detect() else {
+            return;
+        };
+        for polynomial in [0x04C11DB7, CASTAGNOLI_POLYNOMIAL, 0x814141AB, 0x000000AF] {
+            let keys = PclmulKeys::new(polynomial);
+            for len in [0, 127, 128, 129, 143, 144, 200, 1024, data.len()] {
+                let input = &data[..len];
+                let (state, tail) = pclmul.update(&keys, 0x89AB_CDEF, input);
+                assert_eq!(
+                    update_bitwise(state, polynomial, tail),
+                    update_bitwise(0x89AB_CDEF, polynomial, input),
+                    "polynomial: {polynomial:#010x}, len: {len}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sse42_rejects_other_polynomials() {
+        let ieee = Algorithm32::new("crc32", 0x04C11DB7, 0xFFFF_FFFF, 0xFFFF_FFFF, true, true);
+        assert!(!Accel32::new(&ieee).castagnoli);
+        assert!(Accel32::new(&CASTAGNOLI).castagnoli);
+    }
+}
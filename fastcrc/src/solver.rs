@@ -0,0 +1,548 @@
+// Copyright 2024 FastLabs Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Recovery of CRC parameters from sample messages, in the spirit of RevEng.
+//!
+//! For two messages of equal length, `init` and `xor_out` cancel out of the
+//! XOR of their CRCs, leaving `D(x) * x^w + R(x)` divisible by the generator,
+//! where `D` is the XOR of the messages and `R` the XOR of the registers. The
+//! GCD of these differences therefore holds the generator as a factor, and
+//! is usually the generator itself once a few pairs are known. With the
+//! polynomial fixed, every sample is a linear equation in `init` and
+//! `xor_out`, solved by Gaussian elimination over GF(2).
+
+use crate::combine::{mul_mod, x_pow_bytes};
+use crate::core::{reflect_bits, width_mask};
+use crate::registry::{AnyAlgorithm, Entry, CATALOGUE};
+use crate::Algorithm32;
+
+/// Largest degree of the cofactor left next to the generator in the GCD for
+/// which every factorization is tried; beyond it only catalogue polynomials
+/// are considered.
+const MAX_COFACTOR_DEGREE: usize = 16;
+
+/// Name given to solved algorithms missing from the catalogue.
+const UNKNOWN_NAME: &str = "unknown";
+
+/// Parameters reproducing every sample, with the catalogue entry they match.
+#[derive(Clone, Copy, Debug)]
+pub struct Solution {
+    algorithm: Algorithm32,
+    entry: Option<&'static Entry>,
+}
+
+impl Solution {
+    /// The solved parameters, named like the crate's constant when known.
+    pub fn algorithm(&self) -> Algorithm32 {
+        self.algorithm
+    }
+
+    /// The built-in catalogue entry with these parameters, if any.
+    pub fn entry(&self) -> Option<&'static Entry> {
+        self.entry
+    }
+}
+
+/// Searches for the parameters of a CRC of up to 32 bits from sample
+/// messages and their CRCs.
+///
+/// At least two samples of the same length are needed to find the
+/// polynomial, and a third of that length usually pins it down. `init` and
+/// `xor_out` can only be told apart with samples of different lengths;
+/// otherwise the solutions with an `init` of all zeros or all ones, plus any
+/// matching catalogue entries, are reported.
+///
+/// ```
+/// use fastcrc::{Solver, CRC32_BZIP2};
+///
+/// let mut solver = Solver::new(32);
+/// for message in [&b"123456789"[..], b"abcdefghi", b"ABCDEFGHI", b"hello"] {
+///     solver.add_sample(message, CRC32_BZIP2.checksum(message));
+/// }
+/// let solutions = solver.solve();
+/// assert_eq!(solutions.len(), 1);
+/// assert_eq!(solutions[0].algorithm(), CRC32_BZIP2);
+/// assert_eq!(solutions[0].entry().unwrap().name(), "CRC-32/BZIP2");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Solver {
+    width: u8,
+    samples: Vec<(Vec<u8>, u32)>,
+}
+
+impl Solver {
+    /// Create a solver for CRCs of `width` bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is zero or exceeds 32.
+    pub fn new(width: u8) -> Self {
+        assert!((1..=32).contains(&width), "CRC width must fit the register");
+        Self {
+            width,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Add a message together with its CRC.
+    pub fn add_sample(&mut self, message: &[u8], crc: u32) -> &mut Self {
+        self.samples
+            .push((message.to_vec(), crc & width_mask::<u32>(self.width)));
+        self
+    }
+
+    /// Return every parameter set reproducing all samples.
+    ///
+    /// Solutions are ordered by reflection and polynomial; an empty result
+    /// means no CRC fits the samples or there were too few of them.
+    pub fn solve(&self) -> Vec<Solution> {
+        let mut solutions = Vec::new();
+        for (reflect_in, reflect_out) in
+            [(false, false), (true, true), (true, false), (false, true)]
+        {
+            for polynomial in self.polynomials(reflect_in, reflect_out) {
+                for (init, xor_out) in self.offsets(polynomial, reflect_in, reflect_out) {
+                    let params = Algorithm32::with_width(
+                        UNKNOWN_NAME,
+                        self.width,
+                        polynomial,
+                        init,
+                        xor_out,
+                        reflect_in,
+                        reflect_out,
+                    );
+                    if self.reproduces(params) && !solutions.contains(&params) {
+                        solutions.push(params);
+                    }
+                }
+            }
+        }
+        solutions
+            .into_iter()
+            .map(|mut algorithm| {
+                let entry = catalogue_entry(algorithm);
+                if let Some(known) = entry.and_then(|entry| widen(entry.algorithm())) {
+                    algorithm.name = known.name;
+                }
+                Solution { algorithm, entry }
+            })
+            .collect()
+    }
+
+    /// Candidate generators (without the `x^w` term) for the given
+    /// reflection; like every catalogued CRC, they must have a `+ 1` term.
+    fn polynomials(&self, reflect_in: bool, reflect_out: bool) -> Vec<u32> {
+        let mut gcd: Option<Poly> = None;
+        let mut samples: Vec<_> = self.samples.iter().collect();
+        samples.sort_by_key(|(message, _)| message.len());
+        for pair in samples.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if a.0.len() != b.0.len() {
+                continue;
+            }
+            let difference = self.difference(a, b, reflect_in, reflect_out);
+            gcd = Some(match gcd {
+                Some(gcd) => gcd.gcd(difference),
+                None => difference,
+            });
+        }
+        let Some(gcd) = gcd.filter(|gcd| !gcd.is_zero()) else {
+            return Vec::new();
+        };
+        let width = usize::from(self.width);
+        let Some(cofactor_degree) = gcd.degree().checked_sub(width) else {
+            return Vec::new();
+        };
+
+        let mut polynomials = Vec::new();
+        if cofactor_degree <= MAX_COFACTOR_DEGREE {
+            for cofactor in (1u64 << cofactor_degree)..(2u64 << cofactor_degree) {
+                let (quotient, remainder) = gcd.div_rem(&Poly::from_u64(cofactor));
+                let polynomial = quotient.low_u32(width);
+                if remainder.is_zero() && polynomial & 1 != 0 {
+                    polynomials.push(polynomial);
+                }
+            }
+        } else {
+            for entry in CATALOGUE {
+                let Some(params) = widen(entry.algorithm()) else {
+                    continue;
+                };
+                let generator = Poly::from_u64(u64::from(params.polynomial) | 1 << width);
+                if params.width == self.width
+                    && !polynomials.contains(&params.polynomial)
+                    && gcd.div_rem(&generator).1.is_zero()
+                {
+                    polynomials.push(params.polynomial);
+                }
+            }
+        }
+        polynomials.sort_unstable();
+        polynomials
+    }
+
+    /// Build `D(x) * x^w + R(x)` for two samples of equal length.
+    fn difference(
+        &self,
+        (a, crc_a): &(Vec<u8>, u32),
+        (b, crc_b): &(Vec<u8>, u32),
+        reflect_in: bool,
+        reflect_out: bool,
+    ) -> Poly {
+        let width = usize::from(self.width);
+        let bits = a.len() * 8;
+        let mut poly = Poly::from_u64(u64::from(self.register(crc_a ^ crc_b, reflect_out)));
+        for (i, (x, y)) in a.iter().zip(b).enumerate() {
+            let byte = if reflect_in {
+                (x ^ y).reverse_bits()
+            } else {
+                x ^ y
+            };
+            for bit in 0..8 {
+                if byte & (0x80 >> bit) != 0 {
+                    poly.flip(width + bits - 1 - (i * 8 + bit));
+                }
+            }
+        }
+        poly
+    }
+
+    /// Candidate `(init, xor_out)` pairs for `polynomial`.
+    fn offsets(&self, polynomial: u32, reflect_in: bool, reflect_out: bool) -> Vec<(u32, u32)> {
+        let width = usize::from(self.width);
+        let mask = width_mask::<u32>(self.width);
+        let bare = Algorithm32::with_width("", self.width, polynomial, 0, 0, reflect_in, false);
+
+        // Unknowns: bits 0..w hold `init`, bits w..2w the register image of
+        // `xor_out`. Each sample contributes one equation per CRC bit.
+        let mut equations = Vec::new();
+        for (message, crc) in &self.samples {
+            let shift = x_pow_bytes(message.len() as u64, bare);
+            let columns: Vec<u32> = (0..width).map(|j| mul_mod(1 << j, shift, bare)).collect();
+            let rhs = self.register(*crc, reflect_out) ^ bare.checksum(message);
+            for bit in 0..width {
+                let mut row = 1u64 << (width + bit);
+                for (j, column) in columns.iter().enumerate() {
+                    row |= u64::from(column >> bit & 1) << j;
+                }
+                equations.push(Equation {
+                    row,
+                    rhs: rhs >> bit & 1 != 0,
+                });
+            }
+        }
+
+        let unknowns = 2 * width;
+        let split = |solution: u64| {
+            let init = solution as u32 & mask;
+            let xor_out = self.register((solution >> width) as u32, reflect_out);
+            (init, xor_out)
+        };
+        if let Some(solution) = solve_linear(equations.clone(), unknowns) {
+            return vec![split(solution)];
+        }
+
+        let mut inits = vec![0, mask];
+        inits.extend(CATALOGUE.iter().filter_map(|entry| {
+            let params = widen(entry.algorithm())?;
+            (params.width == self.width
+                && params.polynomial == polynomial
+                && params.reflect_in == reflect_in
+                && params.reflect_out == reflect_out)
+                .then_some(params.init)
+        }));
+        let mut offsets = Vec::new();
+        for init in inits {
+            let mut constrained = equations.clone();
+            constrained.extend((0..width).map(|j| Equation {
+                row: 1 << j,
+                rhs: init >> j & 1 != 0,
+            }));
+            if let Some(solution) = solve_linear(constrained, unknowns) {
+                if !offsets.contains(&split(solution)) {
+                    offsets.push(split(solution));
+                }
+            }
+        }
+        offsets
+    }
+
+    /// Map a CRC value to its register polynomial, ignoring `xor_out`.
+    fn register(&self, value: u32, reflect_out: bool) -> u32 {
+        if reflect_out {
+            reflect_bits(value, self.width)
+        } else {
+            value
+        }
+    }
+
+    fn reproduces(&self, params: Algorithm32) -> bool {
+        self.samples
+            .iter()
+            .all(|(message, crc)| params.checksum(message) == *crc)
+    }
+}
+
+/// Find the catalogue entry with exactly the parameters of `params`.
+fn catalogue_entry(params: Algorithm32) -> Option<&'static Entry> {
+    CATALOGUE.iter().find(|entry| {
+        widen(entry.algorithm()).is_some_and(|other| {
+            other.width == params.width
+                && other.polynomial == params.polynomial
+                && other.init == params.init
+                && other.xor_out == params.xor_out
+                && other.reflect_in == params.reflect_in
+                && other.reflect_out == params.reflect_out
+        })
+    })
+}
+
+/// View an algorithm of at most 32 bits as an [`Algorithm32`].
+fn widen(algorithm: AnyAlgorithm) -> Option<Algorithm32> {
+    macro_rules! widen {
+        ($params:expr) => {
+            Algorithm32::with_width(
+                $params.name,
+                $params.width,
+                $params.polynomial.into(),
+                $params.init.into(),
+                $params.xor_out.into(),
+                $params.reflect_in,
+                $params.reflect_out,
+            )
+        };
+    }
+    match algorithm {
+        AnyAlgorithm::Crc8(params) => Some(widen!(params)),
+        AnyAlgorithm::Crc16(params) => Some(widen!(params)),
+        AnyAlgorithm::Crc32(params) => Some(params),
+        AnyAlgorithm::Crc64(_) => None,
+    }
+}
+
+/// A linear equation over GF(2): the XOR of the unknowns selected by `row`
+/// equals `rhs`.
+#[derive(Clone, Copy)]
+struct Equation {
+    row: u64,
+    rhs: bool,
+}
+
+/// Solve a system of at most 64 unknowns, returning the solution only if it
+/// is consistent and unique.
+fn solve_linear(mut equations: Vec<Equation>, unknowns: usize) -> Option<u64> {
+    let mut pivots = Vec::with_capacity(unknowns);
+    for column in 0..unknowns {
+        let bit = 1u64 << column;
+        let rank = pivots.len();
+        let Some(found) = equations[rank..].iter().position(|eq| eq.row & bit != 0) else {
+            continue;
+        };
+        equations.swap(rank, rank + found);
+        let pivot = equations[rank];
+        for (i, eq) in equations.iter_mut().enumerate() {
+            if i != rank && eq.row & bit != 0 {
+                eq.row ^= pivot.row;
+                eq.rhs ^= pivot.rhs;
+            }
+        }
+        pivots.push(column);
+    }
+    if equations[pivots.len()..].iter().any(|eq| eq.rhs) || pivots.len() < unknowns {
+        return None;
+    }
+    Some(
+        equations
+            .iter()
+            .zip(&pivots)
+            .fold(0, |solution, (eq, column)| {
+                solution | u64::from(eq.rhs) << column
+            }),
+    )
+}
+
+/// A polynomial over GF(2) of arbitrary degree; bit `i` is the coefficient
+/// of `x^i`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Poly {
+    words: Vec<u64>,
+}
+
+impl Poly {
+    fn from_u64(value: u64) -> Self {
+        let mut poly = Self { words: vec![value] };
+        poly.trim();
+        poly
+    }
+
+    fn is_zero(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Degree of the polynomial; zero for the zero polynomial.
+    fn degree(&self) -> usize {
+        self.words.last().map_or(0, |top| {
+            (self.words.len() - 1) * 64 + 63 - top.leading_zeros() as usize
+        })
+    }
+
+    /// The coefficients below `x^width`.
+    fn low_u32(&self, width: usize) -> u32 {
+        self.words.first().map_or(0, |&low| low as u32) & width_mask::<u32>(width as u8)
+    }
+
+    fn flip(&mut self, bit: usize) {
+        if self.words.len() <= bit / 64 {
+            self.words.resize(bit / 64 + 1, 0);
+        }
+        self.words[bit / 64] ^= 1 << (bit % 64);
+        self.trim();
+    }
+
+    /// Add `other * x^shift`.
+    fn add_shifted(&mut self, other: &Poly, shift: usize) {
+        let (offset, bits) = (shift / 64, shift % 64);
+        self.words
+            .resize(self.words.len().max(other.words.len() + offset + 1), 0);
+        for (i, &word) in other.words.iter().enumerate() {
+            self.words[i + offset] ^= word << bits;
+            if bits != 0 {
+                self.words[i + offset + 1] ^= word >> (64 - bits);
+            }
+        }
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+
+    fn div_rem(&self, divisor: &Poly) -> (Poly, Poly) {
+        let degree = divisor.degree();
+        let mut quotient = Poly::default();
+        let mut remainder = self.clone();
+        while !remainder.is_zero() && remainder.degree() >= degree {
+            let shift = remainder.degree() - degree;
+            quotient.flip(shift);
+            remainder.add_shifted(divisor, shift);
+        }
+        (quotient, remainder)
+    }
+
+    fn gcd(self, other: Poly) -> Poly {
+        let (mut a, mut b) = (self, other);
+        while !b.is_zero() {
+            let remainder = a.div_rem(&b).1;
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CRC16_MODBUS, CRC32, CRC32C, CRC32_CKSUM, CRC32_MEF};
+
+    const MESSAGES: &[&[u8]] = &[
+        b"123456789",
+        b"abcdefghi",
+        b"ABCDEFGHI",
+        b"The quick brown fox jumps over the lazy dog",
+        b"\x00\x01\x02",
+    ];
+
+    fn solve(params: Algorithm32, messages: &[&[u8]]) -> Vec<Solution> {
+        let mut solver = Solver::new(params.width);
+        for message in messages {
+            solver.add_sample(message, params.checksum(message));
+        }
+        solver.solve()
+    }
+
+    #[test]
+    fn solves_catalogue_algorithms() {
+        for params in [CRC32, CRC32C, CRC32_CKSUM, CRC32_MEF] {
+            let solutions = solve(params, MESSAGES);
+            assert_eq!(solutions.len(), 1, "{}", params.name);
+            let solution = solutions[0];
+            assert_eq!(solution.algorithm().polynomial, params.polynomial);
+            assert_eq!(solution.algorithm().init, params.init);
+            assert_eq!(solution.algorithm().xor_out, params.xor_out);
+            assert!(solution.entry().is_some(), "{}", params.name);
+        }
+    }
+
+    #[test]
+    fn solves_narrow_and_unknown_algorithms() {
+        let modbus = widen(AnyAlgorithm::Crc16(CRC16_MODBUS)).unwrap();
+        let solutions = solve(modbus, MESSAGES);
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].algorithm().name, CRC16_MODBUS.name);
+
+        // A made-up 12-bit CRC with mixed reflection, absent from the catalogue.
+        let device = Algorithm32::with_width("device", 12, 0x5A3, 0x123, 0x456, true, false);
+        let solutions = solve(device, MESSAGES);
+        assert_eq!(solutions.len(), 1);
+        let solved = solutions[0].algorithm();
+        assert_eq!(solved.name, UNKNOWN_NAME);
+        assert!(solutions[0].entry().is_none());
+        assert_eq!(
+            (solved.polynomial, solved.init, solved.xor_out),
+            (0x5A3, 0x123, 0x456)
+        );
+        assert_eq!((solved.reflect_in, solved.reflect_out), (true, false));
+    }
+
+    #[test]
+    fn equal_lengths_fall_back_to_conventional_init() {
+        let same_length = &MESSAGES[..3];
+        let solutions = solve(CRC32, same_length);
+        assert!(solutions.iter().any(|solution| solution
+            .entry()
+            .is_some_and(|entry| entry.name() == "CRC-32/ISO-HDLC")));
+        for solution in &solutions {
+            for message in same_length {
+                assert_eq!(
+                    solution.algorithm().checksum(message),
+                    CRC32.checksum(message)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn insufficient_or_inconsistent_samples_yield_nothing() {
+        assert!(solve(CRC32, &[b"123456789"]).is_empty());
+        assert!(solve(CRC32, &[b"123456789", b"hello"]).is_empty());
+        let mut solver = Solver::new(8);
+        solver
+            .add_sample(b"a", 1)
+            .add_sample(b"a", 2)
+            .add_sample(b"b", 3);
+        assert!(solver.solve().is_empty());
+    }
+
+    #[test]
+    fn poly_arithmetic() {
+        let a = Poly::from_u64(0b1011 << 3 ^ 0b1011);
+        let (quotient, remainder) = a.div_rem(&Poly::from_u64(0b1011));
+        assert_eq!(quotient, Poly::from_u64(0b1001));
+        assert!(remainder.is_zero());
+        assert_eq!(a.gcd(Poly::from_u64(0b1011 * 2)), Poly::from_u64(0b1011));
+    }
+}